@@ -0,0 +1,711 @@
+use std::{
+    fs::{read, read_dir},
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+    process::exit,
+    thread,
+};
+
+use reqwest::blocking::multipart::{Form, Part};
+use sha1::{Digest, Sha1};
+use url::Url;
+
+use crate::{
+    cli::{ContentLayout, TorrentAddOptions, TorrentFilterArgs, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{
+    list_torrents,
+    util::{exit_if_expired, POLL_INTERVAL},
+    TorrentInfoResponse,
+};
+
+// Torrents that still aren't listed after this many polls are given up on
+const MAX_ADD_POLLS: u32 = 30;
+
+/// A magnet link or url to add, with the v1 info-hash if it is known before adding
+struct UrlInput {
+    url: String,
+    hash: Option<String>,
+}
+
+/// A torrent file to add, with its v1 info-hash if it has one
+struct FileInput {
+    path: String,
+    file_name: String,
+    data: Vec<u8>,
+    hash: Option<String>,
+}
+
+/// Adds all inputs (urls, torrent files or directories containing torrent files) using a single
+/// request and verifies every torrent with a known info-hash afterwards.
+pub fn add_torrents(info: &RequestInfo, inputs: Vec<String>, options: &TorrentAddOptions) {
+    let mut urls: Vec<UrlInput> = Vec::new();
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for input in inputs {
+        if input == "-" {
+            for line in io::stdin().lock().lines() {
+                let line = line.unwrap();
+                let line = line.trim();
+
+                if !line.is_empty() {
+                    sort_input(line, &mut urls, &mut files, &mut failed);
+                }
+            }
+        } else {
+            sort_input(&input, &mut urls, &mut files, &mut failed);
+        }
+    }
+
+    if options.rename.is_some() && urls.len() + files.len() > 1 {
+        eprintln!("--rename can only be used when adding a single torrent.");
+        exit(1);
+    }
+
+    let mut file_inputs: Vec<FileInput> = Vec::new();
+    for path in files {
+        match read(&path) {
+            Ok(data) => file_inputs.push(FileInput {
+                path: path.display().to_string(),
+                file_name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                hash: torrent_file_hash(&data),
+                data,
+            }),
+            Err(e) => failed.push((
+                path.display().to_string(),
+                format!("Failed reading file: {}", e),
+            )),
+        }
+    }
+
+    let known_hashes: Vec<String> = urls
+        .iter()
+        .filter_map(|u| u.hash.clone())
+        .chain(file_inputs.iter().filter_map(|f| f.hash.clone()))
+        .collect();
+    if !known_hashes.is_empty() {
+        let filter = TorrentFilterArgs {
+            hashes: known_hashes,
+            ..Default::default()
+        };
+        let existing: Vec<String> =
+            list_torrents(info, TorrentSortingOptions::Name, false, None, &filter)
+                .into_iter()
+                .map(|t| t.hash)
+                .collect();
+
+        let mut is_new = |item: &str, hash: &Option<String>| match hash {
+            Some(hash) if existing.contains(hash) => {
+                failed.push((item.to_string(), "Already added".to_string()));
+                false
+            }
+            _ => true,
+        };
+        urls.retain(|u| is_new(&u.url, &u.hash));
+        file_inputs.retain(|f| is_new(&f.path, &f.hash));
+    }
+
+    // Every url and file of the request with its info-hash, if known
+    let items: Vec<(String, Option<String>)> = urls
+        .iter()
+        .map(|u| (u.url.clone(), u.hash.clone()))
+        .chain(file_inputs.iter().map(|f| (f.path.clone(), f.hash.clone())))
+        .collect();
+
+    // Verified by their hash after adding
+    let mut added: Vec<String> = Vec::new();
+    // qBittorrent only reports whether at least one torrent of a request was added,
+    // so torrents with a hash that is unknown before adding (http urls, v2-only torrents)
+    // can't be verified
+    let mut sent: Vec<String> = Vec::new();
+
+    if !items.is_empty() {
+        let mut form = add_options_form(options);
+
+        if !urls.is_empty() {
+            let joined = urls
+                .iter()
+                .map(|u| u.url.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            form = form.text("urls", joined);
+        }
+
+        for f in file_inputs {
+            form = form.part("torrents", Part::bytes(f.data).file_name(f.file_name));
+        }
+
+        if send_add_request(info, form) {
+            let hashes: Vec<String> = items.iter().filter_map(|(_, h)| h.clone()).collect();
+            let listed: Vec<String> = match hashes.is_empty() {
+                true => Vec::new(),
+                false => wait_until_added(info, &hashes)
+                    .into_iter()
+                    .map(|t| t.hash)
+                    .collect(),
+            };
+
+            let unverifiable = items.len() - hashes.len();
+
+            for (item, hash) in items {
+                match hash {
+                    Some(hash) if listed.contains(&hash) => added.push(item),
+                    Some(_) => failed.push((item, "Not added by qBittorrent".to_string())),
+                    // The request only succeeds if at least one torrent was added
+                    None if unverifiable == 1 && listed.is_empty() => added.push(item),
+                    None => sent.push(item),
+                }
+            }
+        } else {
+            failed.extend(
+                items
+                    .into_iter()
+                    .map(|(item, _)| (item, "Rejected by qBittorrent".to_string())),
+            );
+        }
+    }
+
+    for item in &added {
+        println!("Added: {}", item);
+    }
+
+    for item in &sent {
+        println!("Sent: {}", item);
+    }
+
+    for (item, reason) in &failed {
+        eprintln!("Failed: {} ({})", item, reason);
+    }
+
+    let total = added.len() + sent.len() + failed.len();
+    match sent.is_empty() {
+        true => println!("Added {} of {} torrent(s).", added.len(), total),
+        // The request succeeded, so at least one torrent was added
+        false => println!(
+            "Added at least {} of {} torrent(s). {} of them can't be verified, because their info-hash is only known after adding them.",
+            added.len().max(1),
+            total,
+            sent.len()
+        ),
+    }
+}
+
+fn sort_input(
+    input: &str,
+    urls: &mut Vec<UrlInput>,
+    files: &mut Vec<PathBuf>,
+    failed: &mut Vec<(String, String)>,
+) {
+    match classify_input(input) {
+        Ok(TorrentInput::Magnet(magnet)) => urls.push(UrlInput {
+            hash: magnet_v1_hash(&magnet),
+            url: magnet,
+        }),
+        Ok(TorrentInput::Url(url)) => urls.push(UrlInput {
+            url: url.to_string(),
+            hash: None,
+        }),
+        Ok(TorrentInput::InfoHash(hash)) => urls.push(UrlInput {
+            url: info_hash_to_magnet(&hash),
+            hash: v1_hash_to_hex(&hash),
+        }),
+        Ok(TorrentInput::File(path)) => files.push(path),
+        Ok(TorrentInput::Directory(path)) => {
+            if let Err(e) = find_torrent_files(&path, files) {
+                failed.push((
+                    input.to_string(),
+                    format!("Failed reading directory: {}", e),
+                ));
+            }
+        }
+        Err(e) => failed.push((input.to_string(), e)),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TorrentInput {
+    /// A magnet uri with a valid `xt` parameter
+    Magnet(String),
+    /// A http(s) url pointing to a torrent file
+    Url(Url),
+    /// A bare v1 (hex or base32) or v2 (hex) info-hash
+    InfoHash(String),
+    /// An existing local torrent file
+    File(PathBuf),
+    /// An existing local directory that is searched for torrent files
+    Directory(PathBuf),
+}
+
+/// Determines what kind of input was given. Local paths are checked before info-hashes,
+/// so a file that happens to be named like a hash is still added as a file.
+pub fn classify_input(input: &str) -> Result<TorrentInput, String> {
+    let lowercase = input.to_ascii_lowercase();
+
+    if lowercase.starts_with("magnet:") {
+        return validate_magnet(input).map(|_| TorrentInput::Magnet(input.to_string()));
+    }
+
+    if lowercase.starts_with("http://") || lowercase.starts_with("https://") {
+        return Url::parse(input)
+            .map(TorrentInput::Url)
+            .map_err(|e| format!("Invalid url: {}", e));
+    }
+
+    let path = PathBuf::from(input);
+    if path.is_dir() {
+        return Ok(TorrentInput::Directory(path));
+    }
+
+    if path.is_file() {
+        return Ok(TorrentInput::File(path));
+    }
+
+    if is_v1_info_hash(input) || is_v2_info_hash(input) {
+        return Ok(TorrentInput::InfoHash(lowercase));
+    }
+
+    Err("Not a magnet link, http(s) url, info-hash or existing file".to_string())
+}
+
+fn validate_magnet(magnet: &str) -> Result<(), String> {
+    let url = Url::parse(magnet).map_err(|e| format!("Invalid magnet link: {}", e))?;
+
+    // Multiple exact topics are numbered as xt.1, xt.2, ...
+    let topics: Vec<String> = url
+        .query_pairs()
+        .filter(|(key, _)| key == "xt" || key.starts_with("xt."))
+        .map(|(_, value)| value.to_string())
+        .collect();
+
+    if topics.is_empty() {
+        return Err("Magnet link has no xt parameter".to_string());
+    }
+
+    for topic in &topics {
+        let lowercase = topic.to_ascii_lowercase();
+
+        let valid = if let Some(hash) = lowercase.strip_prefix("urn:btih:") {
+            is_v1_info_hash(hash)
+        } else if let Some(multihash) = lowercase.strip_prefix("urn:btmh:") {
+            // Only sha2-256 multihashes (prefix 1220) are used by BitTorrent v2
+            multihash.strip_prefix("1220").is_some_and(is_v2_info_hash)
+        } else {
+            false
+        };
+
+        if !valid {
+            return Err(format!(
+                "Magnet link has an invalid xt parameter: {}",
+                topic
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// 40 hex characters or 32 base32 characters
+fn is_v1_info_hash(hash: &str) -> bool {
+    (hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        || (hash.len() == 32
+            && hash
+                .chars()
+                .all(|c| matches!(c.to_ascii_uppercase(), 'A'..='Z' | '2'..='7')))
+}
+
+/// 64 hex characters
+fn is_v2_info_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The hex v1 info-hash of a (validated) magnet link, as qBittorrent lists it
+fn magnet_v1_hash(magnet: &str) -> Option<String> {
+    let url = Url::parse(magnet).ok()?;
+
+    url.query_pairs()
+        .filter(|(key, _)| key == "xt" || key.starts_with("xt."))
+        .find_map(|(_, value)| {
+            let value = value.to_ascii_lowercase();
+            value.strip_prefix("urn:btih:").and_then(v1_hash_to_hex)
+        })
+}
+
+/// Converts a v1 info-hash (hex or base32) to lowercase hex. None for v2 info-hashes
+fn v1_hash_to_hex(hash: &str) -> Option<String> {
+    if !is_v1_info_hash(hash) {
+        return None;
+    }
+
+    if hash.len() == 40 {
+        return Some(hash.to_ascii_lowercase());
+    }
+
+    let mut hex = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in hash.to_ascii_uppercase().bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            _ => c - b'2' + 26,
+        };
+
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            hex.push_str(&format!("{:02x}", (buffer >> bits) & 0xff));
+        }
+    }
+
+    Some(hex)
+}
+
+fn info_hash_to_magnet(hash: &str) -> String {
+    match is_v2_info_hash(hash) {
+        true => format!("magnet:?xt=urn:btmh:1220{}", hash),
+        false => format!("magnet:?xt=urn:btih:{}", hash),
+    }
+}
+
+/// The hex v1 info-hash (SHA-1 of the bencoded info dictionary) of a torrent file.
+/// None if the file is invalid or a v2-only torrent, which has no v1 info-hash.
+fn torrent_file_hash(data: &[u8]) -> Option<String> {
+    let info = bencode_dict_value(data, b"info")?;
+
+    // Only v1 and hybrid torrents have pieces in the info dictionary
+    bencode_dict_value(info, b"pieces")?;
+
+    Some(
+        Sha1::digest(info)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect(),
+    )
+}
+
+/// The raw bencoded value of a key in a bencoded dictionary
+fn bencode_dict_value<'a>(dict: &'a [u8], key: &[u8]) -> Option<&'a [u8]> {
+    if dict.first() != Some(&b'd') {
+        return None;
+    }
+
+    let mut pos = 1;
+    while *dict.get(pos)? != b'e' {
+        let key_end = bencode_value_end(dict, pos)?;
+        let value_end = bencode_value_end(dict, key_end)?;
+
+        // Keys are strings, encoded as `<length>:<bytes>`
+        let raw_key = &dict[pos..key_end];
+        let colon = raw_key.iter().position(|&b| b == b':')?;
+        if &raw_key[colon + 1..] == key {
+            return Some(&dict[key_end..value_end]);
+        }
+
+        pos = value_end;
+    }
+
+    None
+}
+
+/// The position after the bencoded value starting at `pos`
+fn bencode_value_end(data: &[u8], pos: usize) -> Option<usize> {
+    match *data.get(pos)? {
+        b'i' => Some(pos + data[pos..].iter().position(|&b| b == b'e')? + 1),
+        b'l' | b'd' => {
+            let mut pos = pos + 1;
+            while *data.get(pos)? != b'e' {
+                pos = bencode_value_end(data, pos)?;
+            }
+            Some(pos + 1)
+        }
+        b'0'..=b'9' => {
+            let colon = pos + data[pos..].iter().position(|&b| b == b':')?;
+            let len: usize = std::str::from_utf8(&data[pos..colon]).ok()?.parse().ok()?;
+            let end = colon + 1 + len;
+
+            (end <= data.len()).then_some(end)
+        }
+        _ => None,
+    }
+}
+
+/// Recursively collects all .torrent files in the directory
+fn find_torrent_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries: Vec<PathBuf> = read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_torrent_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("torrent"))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// qBittorrent only reports whether at least one of the torrents in a request was added
+pub fn send_add_request(info: &RequestInfo, form: Form) -> bool {
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/add").unwrap())
+        .multipart(form)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.text().unwrap() == "Ok."
+}
+
+/// qBittorrent adds torrents asynchronously, so they may not be listed right after the request.
+/// Polls until all of them are listed (or a timeout is reached) and returns the listed ones.
+pub fn wait_until_added(info: &RequestInfo, hashes: &[String]) -> Vec<TorrentInfoResponse> {
+    let filter = TorrentFilterArgs {
+        hashes: hashes.to_vec(),
+        ..Default::default()
+    };
+
+    let mut polls = 0;
+    loop {
+        let torrents = list_torrents(info, TorrentSortingOptions::Name, false, None, &filter);
+
+        polls += 1;
+        if torrents.len() >= hashes.len() || polls >= MAX_ADD_POLLS {
+            return torrents;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+pub fn add_options_form(options: &TorrentAddOptions) -> Form {
+    let mut fields: Vec<(&str, String)> = vec![("paused", options.pause.to_string())];
+
+    if let Some(savepath) = &options.savepath {
+        fields.push(("savepath", savepath.clone()));
+    }
+
+    if let Some(category) = &options.category {
+        fields.push(("category", category.clone()));
+    }
+
+    if !options.tags.is_empty() {
+        fields.push(("tags", options.tags.join(",")));
+    }
+
+    if let Some(rename) = &options.rename {
+        fields.push(("rename", rename.clone()));
+    }
+
+    if options.skip_checking {
+        fields.push(("skip_checking", "true".to_string()));
+    }
+
+    if options.sequential {
+        fields.push(("sequentialDownload", "true".to_string()));
+    }
+
+    if options.first_last_piece {
+        fields.push(("firstLastPiecePrio", "true".to_string()));
+    }
+
+    if let Some(layout) = options.content_layout {
+        fields.push(("contentLayout", format!("{:?}", layout)));
+
+        // Older versions of the api only know about root_folder
+        match layout {
+            ContentLayout::Original => {}
+            ContentLayout::Subfolder => fields.push(("root_folder", "true".to_string())),
+            ContentLayout::NoSubfolder => fields.push(("root_folder", "false".to_string())),
+        }
+    }
+
+    if options.auto_tmm {
+        fields.push(("autoTMM", "true".to_string()));
+    }
+
+    if let Some(up_limit) = options.up_limit {
+        fields.push(("upLimit", up_limit.to_string()));
+    }
+
+    if let Some(dl_limit) = options.dl_limit {
+        fields.push(("dlLimit", dl_limit.to_string()));
+    }
+
+    if let Some(ratio_limit) = options.ratio_limit {
+        fields.push(("ratioLimit", ratio_limit.to_string()));
+    }
+
+    if let Some(seeding_time_limit) = options.seeding_time_limit {
+        fields.push(("seedingTimeLimit", seeding_time_limit.to_string()));
+    }
+
+    if let Some(cookie) = &options.cookie {
+        fields.push(("cookie", cookie.clone()));
+    }
+
+    fields
+        .into_iter()
+        .fold(Form::new(), |form, (name, value)| form.text(name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{classify_input, magnet_v1_hash, torrent_file_hash, v1_hash_to_hex, TorrentInput};
+
+    const V1_HASH: &str = "c9e15763f722f23e98a29decdfae341b98d53056";
+    const V2_HASH: &str = "caf1e1c30e81cb361b9ee167c4aa64228a7fa4fa9f6105232b28ad099f3a302e";
+
+    #[test]
+    fn magnet_with_btih() {
+        let magnet = format!("magnet:?xt=urn:btih:{}&dn=test", V1_HASH);
+        assert_eq!(
+            classify_input(&magnet),
+            Ok(TorrentInput::Magnet(magnet.clone()))
+        );
+
+        let base32 = "magnet:?xt=urn:btih:ZHQVOY7XELZD5GFCTXWN7LRUDOMNKMCW";
+        assert_eq!(
+            classify_input(base32),
+            Ok(TorrentInput::Magnet(base32.to_string()))
+        );
+    }
+
+    #[test]
+    fn magnet_with_btmh() {
+        let magnet = format!("magnet:?xt=urn:btmh:1220{}", V2_HASH);
+        assert_eq!(
+            classify_input(&magnet),
+            Ok(TorrentInput::Magnet(magnet.clone()))
+        );
+
+        let hybrid = format!(
+            "magnet:?xt.1=urn:btih:{}&xt.2=urn:btmh:1220{}",
+            V1_HASH, V2_HASH
+        );
+        assert_eq!(
+            classify_input(&hybrid),
+            Ok(TorrentInput::Magnet(hybrid.clone()))
+        );
+    }
+
+    #[test]
+    fn invalid_magnets() {
+        assert!(classify_input("magnet:?dn=no-topic").is_err());
+        assert!(classify_input("magnet:?xt=urn:btih:1234").is_err());
+        assert!(
+            classify_input("magnet:?xt=urn:sha1:c9e15763f722f23e98a29decdfae341b98d53056").is_err()
+        );
+        assert!(classify_input(&format!("magnet:?xt=urn:btmh:1114{}", V2_HASH)).is_err());
+    }
+
+    #[test]
+    fn http_urls() {
+        let url = "https://example.com/file.torrent";
+        assert_eq!(
+            classify_input(url),
+            Ok(TorrentInput::Url(url.parse().unwrap()))
+        );
+
+        assert!(matches!(
+            classify_input("HTTP://example.com/file.torrent"),
+            Ok(TorrentInput::Url(_))
+        ));
+    }
+
+    #[test]
+    fn bare_info_hashes() {
+        assert_eq!(
+            classify_input(&V1_HASH.to_ascii_uppercase()),
+            Ok(TorrentInput::InfoHash(V1_HASH.to_string()))
+        );
+        assert_eq!(
+            classify_input(V2_HASH),
+            Ok(TorrentInput::InfoHash(V2_HASH.to_string()))
+        );
+    }
+
+    #[test]
+    fn local_paths() {
+        assert_eq!(
+            classify_input("Cargo.toml"),
+            Ok(TorrentInput::File(PathBuf::from("Cargo.toml")))
+        );
+        assert_eq!(
+            classify_input("src"),
+            Ok(TorrentInput::Directory(PathBuf::from("src")))
+        );
+    }
+
+    #[test]
+    fn windows_paths_are_not_urls() {
+        assert!(classify_input(r"C:\does\not\exist.torrent").is_err());
+        assert!(classify_input("ftp://example.com/file.torrent").is_err());
+    }
+
+    #[test]
+    fn v1_hashes_as_hex() {
+        assert_eq!(
+            v1_hash_to_hex("ZHQVOY7XELZD5GFCTXWN7LRUDOMNKMCW"),
+            Some(V1_HASH.to_string())
+        );
+        assert_eq!(
+            v1_hash_to_hex(&V1_HASH.to_ascii_uppercase()),
+            Some(V1_HASH.to_string())
+        );
+        assert_eq!(v1_hash_to_hex(V2_HASH), None);
+
+        let hybrid = format!(
+            "magnet:?xt.1=urn:btmh:1220{}&xt.2=urn:btih:{}",
+            V2_HASH, V1_HASH
+        );
+        assert_eq!(magnet_v1_hash(&hybrid), Some(V1_HASH.to_string()));
+        assert_eq!(
+            magnet_v1_hash(&format!("magnet:?xt=urn:btmh:1220{}", V2_HASH)),
+            None
+        );
+    }
+
+    #[test]
+    fn torrent_file_hashes() {
+        let info = [
+            b"d6:lengthi5e4:name5:a.txt12:piece lengthi16384e6:pieces20:".as_slice(),
+            &[1; 20],
+            b"e",
+        ]
+        .concat();
+        let torrent = [
+            b"d8:announce20:http://tracker/annou13:announce-listll3:abcee4:info".as_slice(),
+            &info,
+            b"e",
+        ]
+        .concat();
+
+        assert_eq!(
+            torrent_file_hash(&torrent),
+            Some("7ad3623c66c7933fa81c7a66121de5b9e4b65d5c".to_string())
+        );
+        assert_eq!(torrent_file_hash(&torrent[..torrent.len() - 10]), None);
+        assert_eq!(torrent_file_hash(b"not a torrent"), None);
+
+        // v2-only torrents have no v1 info-hash
+        let v2_only = b"d4:infod9:file treede12:meta versioni2e4:name1:a12:piece lengthi16384eee";
+        assert_eq!(torrent_file_hash(v2_only), None);
+    }
+}
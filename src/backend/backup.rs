@@ -0,0 +1,307 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{read_to_string, write},
+    path::Path,
+    process::exit,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::blocking::multipart::Part;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cli::{TorrentAddOptions, TorrentFilterArgs, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{
+    add_options_form, ensure_categories, export_torrent, fetch_speed_limits, list_categories,
+    list_torrents, send_add_request,
+    util::{confirm, exit_if_expired, DefaultChoice},
+    wait_until_added, CategoryResponse,
+};
+
+/// Everything needed to add the torrents of an instance again
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Backup {
+    pub created_on: i64,
+    pub categories: Vec<CategoryResponse>,
+    pub torrents: Vec<BackupEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub hash: String,
+    pub name: String,
+    pub save_path: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub completed: bool,
+    pub paused: bool,
+    /// Bytes per second, 0 if unlimited
+    pub dl_limit: u64,
+    /// Bytes per second, 0 if unlimited
+    pub up_limit: u64,
+    pub ratio_limit: f64,
+    pub seeding_time_limit: i64,
+    pub inactive_seeding_time_limit: i64,
+    pub seq_dl: bool,
+    pub f_l_piece_prio: bool,
+    pub super_seeding: bool,
+    pub force_start: bool,
+    pub auto_tmm: bool,
+    pub magnet_uri: String,
+    /// The base64 encoded .torrent file, missing if the metadata wasn't downloaded yet
+    pub torrent: Option<String>,
+}
+
+pub fn create_backup(info: &RequestInfo, path: &Path) {
+    if path.exists()
+        && !confirm(
+            &format!("{} already exists. Overwrite it?", path.display()),
+            DefaultChoice::No,
+        )
+    {
+        println!("Cancelled");
+        return;
+    }
+
+    let torrents = list_torrents(
+        info,
+        TorrentSortingOptions::Name,
+        false,
+        None,
+        &TorrentFilterArgs::default(),
+    );
+
+    let limits = match torrents.is_empty() {
+        true => Some(Default::default()),
+        false => fetch_speed_limits(info, "api/v2/torrents/downloadLimit", &torrents).zip(
+            fetch_speed_limits(info, "api/v2/torrents/uploadLimit", &torrents),
+        ),
+    };
+    let (dl_limits, up_limits) = limits.unwrap_or_else(|| {
+        eprintln!("Fetching the speed limits failed, they are not included in the backup");
+        Default::default()
+    });
+
+    let mut entries: Vec<BackupEntry> = Vec::new();
+
+    for t in torrents {
+        let torrent = export_torrent(info, &t.hash).map(|data| STANDARD.encode(data));
+
+        match torrent {
+            Some(_) => println!("Backed up: {}", t.name),
+            None => println!(
+                "Backed up: {} (magnet only, the .torrent file could not be exported)",
+                t.name
+            ),
+        }
+
+        entries.push(BackupEntry {
+            completed: t.progress >= 1.0,
+            paused: t.is_paused(),
+            dl_limit: dl_limits.get(&t.hash).copied().unwrap_or_default(),
+            up_limit: up_limits.get(&t.hash).copied().unwrap_or_default(),
+            tags: t.tag_list(),
+            hash: t.hash,
+            name: t.name,
+            save_path: t.save_path,
+            category: t.category,
+            ratio_limit: t.ratio_limit,
+            seeding_time_limit: t.seeding_time_limit,
+            inactive_seeding_time_limit: t.inactive_seeding_time_limit,
+            seq_dl: t.seq_dl,
+            f_l_piece_prio: t.f_l_piece_prio,
+            super_seeding: t.super_seeding,
+            force_start: t.force_start,
+            auto_tmm: t.auto_tmm,
+            magnet_uri: t.magnet_uri,
+            torrent,
+        });
+    }
+
+    let backup = Backup {
+        created_on: chrono::Utc::now().timestamp(),
+        categories: list_categories(info),
+        torrents: entries,
+    };
+
+    if let Err(e) = write(path, serde_json::to_string_pretty(&backup).unwrap()) {
+        eprintln!("Failed to write {}: {}", path.display(), e);
+        exit(1);
+    }
+
+    println!(
+        "Saved {} torrent(s) and {} category(s) to {}",
+        backup.torrents.len(),
+        backup.categories.len(),
+        path.display()
+    );
+}
+
+pub fn restore_backup(info: &RequestInfo, path: &Path) {
+    let backup: Backup = match read_to_string(path).map(|s| serde_json::from_str(&s)) {
+        Ok(Ok(backup)) => backup,
+        Ok(Err(e)) => {
+            eprintln!("{} is not a valid backup: {}", path.display(), e);
+            exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            exit(1);
+        }
+    };
+
+    ensure_categories(info, backup.categories);
+
+    let existing_torrents: HashSet<String> = list_torrents(
+        info,
+        TorrentSortingOptions::Name,
+        false,
+        None,
+        &TorrentFilterArgs::default(),
+    )
+    .into_iter()
+    .map(|t| t.hash)
+    .collect();
+
+    let mut restored: Vec<&BackupEntry> = Vec::new();
+    let mut skipped = 0;
+
+    for entry in &backup.torrents {
+        if existing_torrents.contains(&entry.hash) {
+            println!("Skipped: {} (already added)", entry.name);
+            skipped += 1;
+            continue;
+        }
+
+        match restore_entry(info, entry) {
+            true => {
+                println!("Added: {}", entry.name);
+                restored.push(entry);
+            }
+            false => eprintln!("Failed: {} (Rejected by qBittorrent)", entry.name),
+        }
+    }
+
+    restore_flags(info, &restored);
+
+    println!(
+        "Restored {} of {} torrent(s), {} already existed.",
+        restored.len(),
+        backup.torrents.len(),
+        skipped
+    );
+}
+
+fn restore_entry(info: &RequestInfo, entry: &BackupEntry) -> bool {
+    let data = entry
+        .torrent
+        .as_ref()
+        .and_then(|torrent| STANDARD.decode(torrent).ok());
+
+    let options = TorrentAddOptions {
+        pause: entry.paused,
+        savepath: Some(entry.save_path.clone()),
+        category: Some(entry.category.clone()).filter(|c| !c.is_empty()),
+        tags: entry.tags.clone(),
+        // The data of a magnet link can't be verified before its metadata is known
+        skip_checking: entry.completed && data.is_some(),
+        sequential: entry.seq_dl,
+        first_last_piece: entry.f_l_piece_prio,
+        auto_tmm: entry.auto_tmm,
+        up_limit: Some(entry.up_limit).filter(|&l| l > 0),
+        dl_limit: Some(entry.dl_limit).filter(|&l| l > 0),
+        ratio_limit: Some(entry.ratio_limit),
+        seeding_time_limit: Some(entry.seeding_time_limit),
+        ..Default::default()
+    };
+
+    let form = add_options_form(&options).text(
+        "inactiveSeedingTimeLimit",
+        entry.inactive_seeding_time_limit.to_string(),
+    );
+
+    let form = match data {
+        Some(data) => form.part(
+            "torrents",
+            Part::bytes(data).file_name(format!("{}.torrent", entry.hash)),
+        ),
+        None => form.text("urls", entry.magnet_uri.clone()),
+    };
+
+    send_add_request(info, form)
+}
+
+/// Super seeding and force start can't be set when adding, so they are applied afterwards
+fn restore_flags(info: &RequestInfo, restored: &[&BackupEntry]) {
+    let hashes = |enabled: fn(&BackupEntry) -> bool| -> Vec<String> {
+        restored
+            .iter()
+            .filter(|e| enabled(e))
+            .map(|e| e.hash.clone())
+            .collect()
+    };
+
+    let flags = [
+        (
+            "api/v2/torrents/setSuperSeeding",
+            "super seeding",
+            hashes(|e| e.super_seeding),
+        ),
+        (
+            "api/v2/torrents/setForceStart",
+            "force start",
+            hashes(|e| e.force_start),
+        ),
+    ];
+
+    let pending: Vec<String> = restored
+        .iter()
+        .filter(|e| e.super_seeding || e.force_start)
+        .map(|e| e.hash.clone())
+        .collect();
+    if pending.is_empty() {
+        return;
+    }
+
+    let listed: HashSet<String> = wait_until_added(info, &pending)
+        .into_iter()
+        .map(|t| t.hash)
+        .collect();
+
+    for entry in restored {
+        if (entry.super_seeding || entry.force_start) && !listed.contains(&entry.hash) {
+            eprintln!(
+                "Failed restoring the options of {}, it didn't show up after adding it",
+                entry.name
+            );
+        }
+    }
+
+    for (endpoint, flag, mut hashes) in flags {
+        hashes.retain(|h| listed.contains(h));
+        if hashes.is_empty() {
+            continue;
+        }
+
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hashes", hashes.join("|"));
+        formdata.insert("value", "true".to_string());
+
+        let res = info
+            .client
+            .post(info.url.join(endpoint).unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        match res.status().is_success() {
+            true => println!("Turned {} on for {} torrent(s).", flag, hashes.len()),
+            false => eprintln!("Turning {} on failed: {}", flag, res.status()),
+        }
+    }
+}
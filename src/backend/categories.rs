@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestInfo;
+
+use super::util::exit_if_expired;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryResponse {
+    pub name: String,
+    pub save_path: String,
+}
+
+pub fn list_categories(info: &RequestInfo) -> Vec<CategoryResponse> {
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/categories").unwrap())
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    let categories: HashMap<String, CategoryResponse> = res.json().unwrap();
+
+    let mut categories: Vec<CategoryResponse> = categories.into_values().collect();
+    categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+    categories
+}
+
+pub fn create_category(info: &RequestInfo, name: String, save_path: Option<String>) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("category", name.clone());
+    formdata.insert("savePath", save_path.unwrap_or_default());
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/createCategory").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if res.status().is_success() {
+        println!("Created category {}.", name);
+    } else {
+        eprintln!(
+            "Creating category {} failed, make sure the name is valid.",
+            name
+        );
+    }
+}
+
+/// Creates the categories that don't exist yet, keeping existing ones as they are
+pub fn ensure_categories(info: &RequestInfo, categories: Vec<CategoryResponse>) {
+    let existing: Vec<String> = list_categories(info).into_iter().map(|c| c.name).collect();
+
+    for category in categories {
+        if !existing.contains(&category.name) {
+            create_category(info, category.name, Some(category.save_path));
+        }
+    }
+}
+
+pub fn edit_category(info: &RequestInfo, name: String, save_path: String) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("category", name.clone());
+    formdata.insert("savePath", save_path);
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/editCategory").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if res.status().is_success() {
+        println!("Updated category {}.", name);
+    } else {
+        eprintln!("Editing category {} failed, make sure it exists.", name);
+    }
+}
+
+pub fn remove_categories(info: &RequestInfo, names: Vec<String>) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("categories", names.join("\n"));
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/removeCategories").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    println!("Sent request to remove {} category(s).", names.len());
+}
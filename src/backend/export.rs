@@ -0,0 +1,102 @@
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use reqwest::blocking::Response;
+
+use crate::config::RequestInfo;
+
+use super::{
+    util::{exit_if_expired, exit_if_unsupported},
+    TorrentInfoResponse,
+};
+
+/// Downloads the .torrent file of a torrent. Fails for magnet links whose metadata isn't known yet
+/// and on servers that don't support exporting
+pub fn export_torrent(info: &RequestInfo, hash: &str) -> Option<Vec<u8>> {
+    let res = send_export_request(info, hash);
+
+    if !res.status().is_success() {
+        return None;
+    }
+
+    res.bytes().ok().map(|bytes| bytes.to_vec())
+}
+
+fn send_export_request(info: &RequestInfo, hash: &str) -> Response {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", hash.to_string());
+
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/export").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res
+}
+
+pub fn export_torrents(info: &RequestInfo, torrents: &[TorrentInfoResponse], out: &Path) {
+    if let Err(e) = create_dir_all(out) {
+        eprintln!("Failed to create {}: {}", out.display(), e);
+        exit(1);
+    }
+
+    let mut exported = 0;
+
+    for t in torrents {
+        let res = send_export_request(info, &t.hash);
+        exit_if_unsupported(&res);
+
+        let Some(data) = res
+            .status()
+            .is_success()
+            .then(|| res.bytes().ok())
+            .flatten()
+        else {
+            eprintln!(
+                "Failed: {} (make sure its metadata has been downloaded)",
+                t.name
+            );
+            continue;
+        };
+
+        let path = unique_path(out, &t.name);
+        match write(&path, data) {
+            Ok(()) => {
+                exported += 1;
+                println!("Exported: {} -> {}", t.name, path.display());
+            }
+            Err(e) => eprintln!("Failed: {} ({})", t.name, e),
+        }
+    }
+
+    println!("Exported {} of {} torrent(s).", exported, torrents.len());
+}
+
+/// A path for `<name>.torrent` in the directory, appending a number if the file already exists
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    // Torrent names may contain characters that aren't allowed in file names on some systems
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let mut path = dir.join(format!("{}.torrent", name));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{} ({}).torrent", name, n));
+        n += 1;
+    }
+
+    path
+}
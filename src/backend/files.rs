@@ -0,0 +1,171 @@
+use std::{collections::HashMap, process::exit};
+
+use regex::Regex;
+
+use crate::{cli::FilePriority, config::RequestInfo};
+
+use super::{
+    torrent_content,
+    util::{exit_if_expired, glob_to_regex},
+    TorrentFileResponse, TorrentInfoResponse,
+};
+
+/// Finds the files of the torrent matching any of the patterns, which are either file indices or globs
+fn match_files<'a>(
+    files: &'a [TorrentFileResponse],
+    patterns: &[String],
+) -> Vec<&'a TorrentFileResponse> {
+    let mut matched: Vec<&TorrentFileResponse> = Vec::new();
+
+    for pattern in patterns {
+        let matches: Vec<&TorrentFileResponse> = match pattern.parse::<u64>() {
+            Ok(index) => files.iter().filter(|f| f.index == index).collect(),
+            Err(_) => {
+                let regex = glob_to_regex(pattern);
+                files.iter().filter(|f| regex.is_match(&f.name)).collect()
+            }
+        };
+
+        if matches.is_empty() {
+            eprintln!("No file of the torrent matches {}", pattern);
+            exit(1);
+        }
+
+        for file in matches {
+            if !matched.iter().any(|f| f.index == file.index) {
+                matched.push(file);
+            }
+        }
+    }
+
+    matched
+}
+
+pub fn set_file_priority(
+    info: &RequestInfo,
+    torrent: &TorrentInfoResponse,
+    patterns: &[String],
+    priority: FilePriority,
+) {
+    let Some(files) = torrent_content(info, torrent.hash.clone()) else {
+        eprintln!("Request failed, make sure the hash is valid");
+        return;
+    };
+
+    let matched = match_files(&files, patterns);
+
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hash", torrent.hash.clone());
+    formdata.insert(
+        "id",
+        matched
+            .iter()
+            .map(|f| f.index.to_string())
+            .collect::<Vec<_>>()
+            .join("|"),
+    );
+    formdata.insert("priority", priority.api_value().to_string());
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/filePrio").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if !res.status().is_success() {
+        eprintln!("Setting the file priority failed: {}", res.status());
+        return;
+    }
+
+    println!(
+        "Set the priority of {} file(s) to {:?}:",
+        matched.len(),
+        priority
+    );
+    for file in matched {
+        println!("  {} {}", file.index, file.name);
+    }
+}
+
+pub fn rename_file(info: &RequestInfo, hash: &str, old_path: &str, new_path: &str) -> bool {
+    send_rename(info, "api/v2/torrents/renameFile", hash, old_path, new_path)
+}
+
+pub fn rename_folder(info: &RequestInfo, hash: &str, old_path: &str, new_path: &str) -> bool {
+    send_rename(
+        info,
+        "api/v2/torrents/renameFolder",
+        hash,
+        old_path,
+        new_path,
+    )
+}
+
+fn send_rename(
+    info: &RequestInfo,
+    endpoint: &str,
+    hash: &str,
+    old_path: &str,
+    new_path: &str,
+) -> bool {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hash", hash.to_string());
+    formdata.insert("oldPath", old_path.to_string());
+    formdata.insert("newPath", new_path.to_string());
+
+    let res = info
+        .client
+        .post(info.url.join(endpoint).unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.status().is_success()
+}
+
+/// Renames every file of the torrent whose path matches the pattern
+pub fn rename_files_regex(
+    info: &RequestInfo,
+    torrent: &TorrentInfoResponse,
+    pattern: &Regex,
+    replacement: &str,
+    dry_run: bool,
+) {
+    let Some(files) = torrent_content(info, torrent.hash.clone()) else {
+        eprintln!("Request failed, make sure the hash is valid");
+        return;
+    };
+
+    let mut renamed = 0;
+    let mut failed = 0;
+
+    for file in &files {
+        let new_name = pattern.replace_all(&file.name, replacement);
+        if new_name == file.name {
+            continue;
+        }
+
+        let result = if dry_run {
+            "dry run"
+        } else if rename_file(info, &torrent.hash, &file.name, &new_name) {
+            renamed += 1;
+            "ok"
+        } else {
+            failed += 1;
+            "failed"
+        };
+
+        println!("  {} -> {} [{}]", file.name, new_name, result);
+    }
+
+    if dry_run {
+        println!("\nDry run, nothing was renamed.");
+    } else {
+        println!("\nRenamed {} file(s), {} failed.", renamed, failed);
+    }
+}
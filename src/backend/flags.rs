@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::{cli::TorrentFlags, config::RequestInfo};
+
+use super::{hashes_param, util::exit_if_expired, TorrentInfoResponse};
+
+pub fn set_torrent_flags(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    flags: &TorrentFlags,
+) {
+    // Sequential download and first/last piece priority can only be toggled, so only the
+    // torrents that are not already in the wanted state are sent
+    if let Some(switch) = flags.sequential {
+        toggle(
+            info,
+            "api/v2/torrents/toggleSequentialDownload",
+            "sequential download",
+            torrents,
+            switch.is_on(),
+            |t| t.seq_dl,
+        );
+    }
+
+    if let Some(switch) = flags.first_last_piece {
+        toggle(
+            info,
+            "api/v2/torrents/toggleFirstLastPiecePrio",
+            "first/last piece priority",
+            torrents,
+            switch.is_on(),
+            |t| t.f_l_piece_prio,
+        );
+    }
+
+    if let Some(switch) = flags.super_seeding {
+        set(
+            info,
+            "api/v2/torrents/setSuperSeeding",
+            "super seeding",
+            "value",
+            torrents,
+            all,
+            switch.is_on(),
+        );
+    }
+
+    if let Some(switch) = flags.force_start {
+        set(
+            info,
+            "api/v2/torrents/setForceStart",
+            "force start",
+            "value",
+            torrents,
+            all,
+            switch.is_on(),
+        );
+    }
+
+    if let Some(switch) = flags.auto_tmm {
+        set(
+            info,
+            "api/v2/torrents/setAutoManagement",
+            "automatic torrent management",
+            "enable",
+            torrents,
+            all,
+            switch.is_on(),
+        );
+    }
+}
+
+fn toggle(
+    info: &RequestInfo,
+    endpoint: &str,
+    flag: &str,
+    torrents: &[TorrentInfoResponse],
+    on: bool,
+    current: impl Fn(&TorrentInfoResponse) -> bool,
+) {
+    let to_toggle: Vec<&TorrentInfoResponse> =
+        torrents.iter().filter(|t| current(t) != on).collect();
+
+    if !to_toggle.is_empty() {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert(
+            "hashes",
+            to_toggle
+                .iter()
+                .map(|t| t.hash.as_str())
+                .collect::<Vec<_>>()
+                .join("|"),
+        );
+
+        let res = info
+            .client
+            .post(info.url.join(endpoint).unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        if !res.status().is_success() {
+            eprintln!(
+                "Turning {} {} failed: {}",
+                flag,
+                switch_name(on),
+                res.status()
+            );
+            return;
+        }
+    }
+
+    println!(
+        "Turned {} {} for {} torrent(s), {} already were.",
+        flag,
+        switch_name(on),
+        to_toggle.len(),
+        torrents.len() - to_toggle.len()
+    );
+}
+
+fn set(
+    info: &RequestInfo,
+    endpoint: &str,
+    flag: &str,
+    value_field: &str,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    on: bool,
+) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hashes", hashes_param(torrents, all));
+    formdata.insert(value_field, on.to_string());
+
+    let res = info
+        .client
+        .post(info.url.join(endpoint).unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if !res.status().is_success() {
+        eprintln!(
+            "Turning {} {} failed: {}",
+            flag,
+            switch_name(on),
+            res.status()
+        );
+        return;
+    }
+
+    println!(
+        "Turned {} {} for {} torrent(s).",
+        flag,
+        switch_name(on),
+        torrents.len()
+    );
+}
+
+fn switch_name(on: bool) -> &'static str {
+    match on {
+        true => "on",
+        false => "off",
+    }
+}
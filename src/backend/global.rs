@@ -1,6 +1,8 @@
-use serde::Deserialize;
+use std::collections::HashMap;
 
-use crate::{backend::util::epoch_to_datetime, config::RequestInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestInfo;
 
 use super::util::exit_if_expired;
 
@@ -28,40 +30,36 @@ pub fn version(info: &RequestInfo) {
     println!("The qBittorrent app is running: {}", text);
 }
 
-#[derive(Debug, Deserialize)]
-struct LogResponse {
-    id: u32,
-    message: String,
-    timestamp: i64,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogResponse {
+    pub id: u32,
+    pub message: String,
+    pub timestamp: i64,
     // r# to use type as an identifier
-    r#type: u32,
+    pub r#type: u32,
 }
 
-pub fn logs(info: &RequestInfo) {
-    let res = info
-        .client
-        .post(info.url.join("api/v2/log/main").unwrap())
-        .send()
-        .unwrap();
-    exit_if_expired(&res);
-
-    let logs: Vec<LogResponse> = res.json().unwrap();
-
-    println!("ID\tTYPE\tTIME\t\t\tMESSAGE\n");
-
-    for log in &logs {
-        let level = match log.r#type {
+impl LogResponse {
+    pub fn level(&self) -> &'static str {
+        match self.r#type {
             1 => "NORM",
             2 => "INFO",
             4 => "WARN",
             8 => "CRIT",
             _ => "UNKNOWN",
-        };
+        }
+    }
+}
 
-        let time = epoch_to_datetime(log.timestamp);
+pub fn logs(info: &RequestInfo) -> Vec<LogResponse> {
+    let res = info
+        .client
+        .post(info.url.join("api/v2/log/main").unwrap())
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
 
-        println!("{}\t{}\t{}\t{}", log.id, level, time, log.message);
-    }
+    res.json().unwrap()
 }
 
 pub fn get_alt_speed(info: &RequestInfo) -> String {
@@ -97,3 +95,19 @@ pub fn toggle_alt_speed(info: &RequestInfo) {
         get_alt_speed(info)
     )
 }
+
+pub fn ban_peers(info: &RequestInfo, peers: Vec<String>) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("peers", peers.join("|"));
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/transfer/banPeers").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    println!("Sent request to ban {} peer(s).", peers.len());
+}
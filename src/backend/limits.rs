@@ -0,0 +1,174 @@
+use std::{collections::HashMap, process::exit};
+
+use serde::Serialize;
+
+use crate::config::RequestInfo;
+
+use super::{hashes_param, util::exit_if_expired, TorrentInfoResponse};
+
+#[derive(Debug, Serialize)]
+pub struct TorrentLimits {
+    pub hash: String,
+    pub name: String,
+    /// Bytes per second, 0 if unlimited
+    pub dl_limit: u64,
+    /// Bytes per second, 0 if unlimited
+    pub up_limit: u64,
+    pub ratio_limit: f64,
+    pub seeding_time_limit: i64,
+    pub inactive_seeding_time_limit: i64,
+}
+
+pub fn get_limits(info: &RequestInfo, torrents: Vec<TorrentInfoResponse>) -> Vec<TorrentLimits> {
+    let fetch = |endpoint| match fetch_speed_limits(info, endpoint, &torrents) {
+        Some(limits) => limits,
+        None => {
+            eprintln!("Fetching the speed limits failed");
+            exit(1);
+        }
+    };
+    let dl_limits = fetch("api/v2/torrents/downloadLimit");
+    let up_limits = fetch("api/v2/torrents/uploadLimit");
+
+    torrents
+        .into_iter()
+        .map(|t| TorrentLimits {
+            dl_limit: dl_limits.get(&t.hash).copied().unwrap_or_default(),
+            up_limit: up_limits.get(&t.hash).copied().unwrap_or_default(),
+            ratio_limit: t.ratio_limit,
+            seeding_time_limit: t.seeding_time_limit,
+            inactive_seeding_time_limit: t.inactive_seeding_time_limit,
+            hash: t.hash,
+            name: t.name,
+        })
+        .collect()
+}
+
+pub fn fetch_speed_limits(
+    info: &RequestInfo,
+    endpoint: &str,
+    torrents: &[TorrentInfoResponse],
+) -> Option<HashMap<String, u64>> {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hashes", hashes_param(torrents, false));
+
+    let res = info
+        .client
+        .post(info.url.join(endpoint).unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    let limits: HashMap<String, i64> = res.json().ok()?;
+
+    // Older servers report unlimited as -1 instead of 0
+    Some(
+        limits
+            .into_iter()
+            .map(|(hash, limit)| (hash, limit.max(0) as u64))
+            .collect(),
+    )
+}
+
+pub fn set_speed_limits(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    dl_limit: Option<u64>,
+    up_limit: Option<u64>,
+) {
+    let limits = [
+        ("api/v2/torrents/setDownloadLimit", "download", dl_limit),
+        ("api/v2/torrents/setUploadLimit", "upload", up_limit),
+    ];
+
+    for (endpoint, direction, limit) in limits {
+        let Some(limit) = limit else {
+            continue;
+        };
+
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hashes", hashes_param(torrents, all));
+        formdata.insert("limit", limit.to_string());
+
+        let res = info
+            .client
+            .post(info.url.join(endpoint).unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        if !res.status().is_success() {
+            eprintln!("Setting the {} limit failed: {}", direction, res.status());
+            continue;
+        }
+
+        println!(
+            "Sent request to set the {} limit of {} torrent(s).",
+            direction,
+            torrents.len()
+        );
+    }
+}
+
+/// Ratio, seeding time and inactive seeding time
+type ShareLimits = (f64, i64, i64);
+
+/// The api requires all three share limits at once, so limits that are not given are kept
+/// at the current value of each torrent. Torrents ending up with the same limits share a request.
+pub fn set_share_limits(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    ratio_limit: Option<f64>,
+    seeding_time_limit: Option<i64>,
+    inactive_seeding_time_limit: Option<i64>,
+) {
+    let mut groups: Vec<(ShareLimits, Vec<&str>)> = Vec::new();
+
+    for t in torrents {
+        let limits = (
+            ratio_limit.unwrap_or(t.ratio_limit),
+            seeding_time_limit.unwrap_or(t.seeding_time_limit),
+            inactive_seeding_time_limit.unwrap_or(t.inactive_seeding_time_limit),
+        );
+
+        match groups.iter_mut().find(|(l, _)| *l == limits) {
+            Some((_, hashes)) => hashes.push(&t.hash),
+            None => groups.push((limits, vec![&t.hash])),
+        }
+    }
+
+    for ((ratio, seeding_time, inactive_seeding_time), hashes) in groups {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hashes", hashes.join("|"));
+        formdata.insert("ratioLimit", ratio.to_string());
+        formdata.insert("seedingTimeLimit", seeding_time.to_string());
+        formdata.insert(
+            "inactiveSeedingTimeLimit",
+            inactive_seeding_time.to_string(),
+        );
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/setShareLimits").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        if !res.status().is_success() {
+            eprintln!("Setting the share limits failed: {}", res.status());
+            return;
+        }
+    }
+
+    println!(
+        "Sent request to set the share limits of {} torrent(s).",
+        torrents.len()
+    );
+}
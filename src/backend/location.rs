@@ -0,0 +1,137 @@
+use std::{collections::HashMap, thread};
+
+use crate::{
+    cli::{TorrentFilterArgs, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{
+    hashes_param, list_torrents,
+    util::{exit_if_expired, exit_if_unsupported, TorrentState, POLL_INTERVAL},
+    TorrentInfoResponse,
+};
+
+/// Moves the data of the torrents to the location
+pub fn set_location(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    location: &str,
+) -> bool {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hashes", hashes_param(torrents, all));
+    formdata.insert("location", location.to_string());
+
+    send_location_request(
+        info,
+        "api/v2/torrents/setLocation",
+        formdata,
+        torrents,
+        location,
+    )
+}
+
+pub fn set_save_path(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    path: &str,
+) -> bool {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("id", hashes_param(torrents, all));
+    formdata.insert("path", path.to_string());
+
+    send_location_request(
+        info,
+        "api/v2/torrents/setSavePath",
+        formdata,
+        torrents,
+        path,
+    )
+}
+
+pub fn set_download_path(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    path: &str,
+) -> bool {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("id", hashes_param(torrents, all));
+    formdata.insert("path", path.to_string());
+
+    send_location_request(
+        info,
+        "api/v2/torrents/setDownloadPath",
+        formdata,
+        torrents,
+        path,
+    )
+}
+
+fn send_location_request(
+    info: &RequestInfo,
+    endpoint: &str,
+    formdata: HashMap<&str, String>,
+    torrents: &[TorrentInfoResponse],
+    path: &str,
+) -> bool {
+    let res = info
+        .client
+        .post(info.url.join(endpoint).unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+    exit_if_unsupported(&res);
+
+    if !res.status().is_success() {
+        eprintln!(
+            "Changing the location to {} failed, make sure the path is not empty and writable ({})",
+            path,
+            res.status()
+        );
+        return false;
+    }
+
+    println!(
+        "Sent request to change the location of {} torrent(s) to {}.",
+        torrents.len(),
+        path
+    );
+    true
+}
+
+/// Polls the torrents until none of them is moving to the path anymore
+pub fn wait_until_moved(info: &RequestInfo, torrents: &[TorrentInfoResponse], path: &str) {
+    let mut pending: Vec<String> = torrents.iter().map(|t| t.hash.clone()).collect();
+
+    // Give qBittorrent a moment to actually start moving
+    thread::sleep(POLL_INTERVAL);
+
+    while !pending.is_empty() {
+        let filter = TorrentFilterArgs {
+            hashes: pending.clone(),
+            ..Default::default()
+        };
+        let current = list_torrents(info, TorrentSortingOptions::Name, false, None, &filter);
+
+        pending.clear();
+        for t in current {
+            if matches!(t.state, TorrentState::Moving) {
+                pending.push(t.hash);
+            } else {
+                println!("Finished moving {} to {}", t.name, path);
+            }
+        }
+
+        if !pending.is_empty() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    println!("All {} torrent(s) finished moving.", torrents.len());
+}
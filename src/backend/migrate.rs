@@ -0,0 +1,171 @@
+use std::{collections::HashSet, thread};
+
+use reqwest::blocking::multipart::Part;
+
+use crate::{
+    cli::{TorrentAddOptions, TorrentFilterArgs, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{
+    add_options_form, delete_torrents, ensure_categories, export_torrent, list_categories,
+    list_torrents, send_add_request,
+    util::{TorrentState, POLL_INTERVAL},
+    wait_until_added, TorrentInfoResponse,
+};
+
+/// Adds the torrents to the destination with the same save path, category and tags.
+/// The data is not transferred, so both instances should see the files at the same path.
+pub fn migrate_torrents(
+    source: &RequestInfo,
+    destination: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    delete: bool,
+) {
+    let mut categories = list_categories(source);
+    categories.retain(|c| torrents.iter().any(|t| t.category == c.name));
+    ensure_categories(destination, categories);
+
+    let existing_torrents: HashSet<String> = list_torrents(
+        destination,
+        TorrentSortingOptions::Name,
+        false,
+        None,
+        &TorrentFilterArgs::default(),
+    )
+    .into_iter()
+    .map(|t| t.hash)
+    .collect();
+
+    let mut migrated: Vec<&TorrentInfoResponse> = Vec::new();
+
+    for t in torrents {
+        if existing_torrents.contains(&t.hash) {
+            println!("Skipped: {} (already added on {})", t.name, destination.url);
+            continue;
+        }
+
+        match migrate_torrent(source, destination, t) {
+            true => {
+                println!("Added: {}", t.name);
+                migrated.push(t);
+            }
+            false => eprintln!("Failed: {} (Rejected by qBittorrent)", t.name),
+        }
+    }
+
+    println!(
+        "Migrated {} of {} torrent(s) from {} to {}.",
+        migrated.len(),
+        torrents.len(),
+        source.url,
+        destination.url
+    );
+
+    if !delete || migrated.is_empty() {
+        return;
+    }
+
+    let verified = wait_until_checked(destination, &migrated);
+    if !verified.is_empty() {
+        delete_torrents(source, verified, false);
+    }
+}
+
+fn migrate_torrent(
+    source: &RequestInfo,
+    destination: &RequestInfo,
+    torrent: &TorrentInfoResponse,
+) -> bool {
+    let options = TorrentAddOptions {
+        pause: torrent.is_paused(),
+        savepath: Some(torrent.save_path.clone()),
+        category: Some(torrent.category.clone()).filter(|c| !c.is_empty()),
+        tags: torrent.tag_list(),
+        ..Default::default()
+    };
+
+    let form = add_options_form(&options);
+
+    // Magnet links without metadata can't be exported
+    let form = match export_torrent(source, &torrent.hash) {
+        Some(data) => form.part(
+            "torrents",
+            Part::bytes(data).file_name(format!("{}.torrent", torrent.hash)),
+        ),
+        None => form.text("urls", torrent.magnet_uri.clone()),
+    };
+
+    send_add_request(destination, form)
+}
+
+/// Polls the destination until none of the torrents is checking anymore and returns the hashes
+/// of those that have at least as much data there as on the source
+fn wait_until_checked(destination: &RequestInfo, torrents: &[&TorrentInfoResponse]) -> Vec<String> {
+    let mut pending: Vec<String> = torrents.iter().map(|t| t.hash.clone()).collect();
+    let mut verified: Vec<String> = Vec::new();
+
+    println!("Waiting for {} to check the torrent(s)...", destination.url);
+
+    let listed: HashSet<String> = wait_until_added(destination, &pending)
+        .into_iter()
+        .map(|t| t.hash)
+        .collect();
+    pending.retain(|hash| {
+        if !listed.contains(hash) {
+            let original = torrents.iter().find(|o| &o.hash == hash).unwrap();
+            eprintln!(
+                "Keeping {} on the source, it didn't show up on the destination",
+                original.name
+            );
+        }
+        listed.contains(hash)
+    });
+
+    while !pending.is_empty() {
+        let filter = TorrentFilterArgs {
+            hashes: pending.clone(),
+            ..Default::default()
+        };
+        let current = list_torrents(
+            destination,
+            TorrentSortingOptions::Name,
+            false,
+            None,
+            &filter,
+        );
+
+        pending.clear();
+        for t in current {
+            if matches!(
+                t.state,
+                TorrentState::CheckingUP
+                    | TorrentState::CheckingDL
+                    | TorrentState::CheckingResumeData
+                    | TorrentState::Allocating
+            ) {
+                pending.push(t.hash);
+                continue;
+            }
+
+            let original = torrents.iter().find(|o| o.hash == t.hash).unwrap();
+            if t.progress >= original.progress {
+                println!("Finished checking {}", t.name);
+                verified.push(t.hash);
+            } else {
+                eprintln!(
+                    "Keeping {} on the source, only {:.1}% of {:.1}% were found on the destination",
+                    t.name,
+                    t.progress * 100.0,
+                    original.progress * 100.0
+                );
+            }
+        }
+
+        if !pending.is_empty() {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    verified
+}
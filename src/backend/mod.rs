@@ -1,6 +1,21 @@
 mod torrents;
 pub use torrents::*;
 
+mod selector;
+pub use selector::*;
+
+mod add;
+pub use add::*;
+
+mod export;
+pub use export::*;
+
+mod backup;
+pub use backup::*;
+
+mod migrate;
+pub use migrate::*;
+
 mod util;
 
 mod auth;
@@ -8,3 +23,33 @@ pub use auth::*;
 
 mod global;
 pub use global::*;
+
+mod categories;
+pub use categories::*;
+
+mod tags;
+pub use tags::*;
+
+mod trackers;
+pub use trackers::*;
+
+mod peers;
+pub use peers::*;
+
+mod webseeds;
+pub use webseeds::*;
+
+mod files;
+pub use files::*;
+
+mod pieces;
+pub use pieces::*;
+
+mod location;
+pub use location::*;
+
+mod limits;
+pub use limits::*;
+
+mod flags;
+pub use flags::*;
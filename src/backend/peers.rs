@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestInfo;
+
+use super::{hashes_param, util::exit_if_expired, TorrentInfoResponse};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeerResponse {
+    pub ip: String,
+    pub port: u16,
+    pub client: String,
+    pub flags: String,
+    pub connection: String,
+    // Only present if geolocation is enabled in qBittorrent
+    #[serde(default)]
+    pub country: String,
+    #[serde(default)]
+    pub country_code: String,
+    pub progress: f64,
+    pub dl_speed: u64,
+    pub up_speed: u64,
+    pub downloaded: u64,
+    pub uploaded: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TorrentPeersResponse {
+    peers: HashMap<String, PeerResponse>,
+}
+
+pub fn list_peers(info: &RequestInfo, hash: &str) -> Option<Vec<PeerResponse>> {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", hash.to_string());
+    // rid 0 always returns the full list instead of changes since the last request
+    query.insert("rid", "0".to_string());
+
+    let res = info
+        .client
+        .get(info.url.join("api/v2/sync/torrentPeers").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    let response: TorrentPeersResponse = res.json().ok()?;
+
+    let mut peers: Vec<PeerResponse> = response.peers.into_values().collect();
+    peers.sort_by(|a, b| b.dl_speed.cmp(&a.dl_speed).then(a.ip.cmp(&b.ip)));
+
+    Some(peers)
+}
+
+pub fn add_peers(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    peers: Vec<String>,
+) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hashes", hashes_param(torrents, all));
+    formdata.insert("peers", peers.join("|"));
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/addPeers").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if !res.status().is_success() {
+        eprintln!("Adding peers failed, make sure they are formatted as host:port");
+        return;
+    }
+
+    println!(
+        "Sent request to add {} peer(s) to {} torrent(s).",
+        peers.len(),
+        torrents.len()
+    );
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::config::RequestInfo;
+
+use super::util::exit_if_expired;
+
+// The states returned by the api, missing pieces are 0
+pub const PIECE_DOWNLOADING: u8 = 1;
+pub const PIECE_HAVE: u8 = 2;
+
+#[derive(Debug, Serialize)]
+pub struct PieceInfo {
+    pub index: usize,
+    pub state: u8,
+    /// Only fetched when requested, as there can be tens of thousands of pieces
+    pub hash: Option<String>,
+}
+
+/// The states of all pieces of a torrent, optionally combined with their hashes
+pub fn torrent_pieces(info: &RequestInfo, hash: &str, with_hashes: bool) -> Option<Vec<PieceInfo>> {
+    let states: Vec<u8> = get_pieces(info, "api/v2/torrents/pieceStates", hash)?;
+    let hashes: Vec<String> = match with_hashes {
+        true => get_pieces(info, "api/v2/torrents/pieceHashes", hash)?,
+        false => Vec::new(),
+    };
+
+    Some(
+        states
+            .into_iter()
+            .enumerate()
+            .map(|(index, state)| PieceInfo {
+                index,
+                state,
+                hash: hashes.get(index).cloned(),
+            })
+            .collect(),
+    )
+}
+
+fn get_pieces<T: serde::de::DeserializeOwned>(
+    info: &RequestInfo,
+    endpoint: &str,
+    hash: &str,
+) -> Option<Vec<T>> {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", hash.to_string());
+
+    let res = info
+        .client
+        .get(info.url.join(endpoint).unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.json().ok()
+}
@@ -0,0 +1,169 @@
+use std::process::exit;
+
+use crate::{
+    cli::{TorrentFilterArgs, TorrentSelector, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{list_torrents, util::glob_to_regex, TorrentInfoResponse};
+
+/// Resolves the selector against the torrents of the instance. Exits if a hash prefix
+/// is ambiguous or doesn't match, or if nothing was selected at all.
+pub fn resolve_torrents(
+    info: &RequestInfo,
+    selector: &TorrentSelector,
+) -> Vec<TorrentInfoResponse> {
+    let filter = TorrentFilterArgs {
+        filter: selector.state,
+        category: selector.category.clone(),
+        tag: selector.tag.clone(),
+        hashes: vec![],
+        name: selector.name.clone(),
+    };
+
+    let mut torrents = list_torrents(info, TorrentSortingOptions::Name, false, None, &filter);
+
+    if let Some(glob) = &selector.glob {
+        let regex = glob_to_regex(glob);
+        torrents.retain(|t| regex.is_match(&t.name));
+    }
+
+    let torrents = match selector.hashes.is_empty() {
+        true => torrents,
+        false => match_prefixes(torrents, &selector.hashes).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1);
+        }),
+    };
+
+    if torrents.is_empty() {
+        eprintln!("No torrents matched the selection.");
+        exit(1);
+    }
+
+    torrents
+}
+
+/// Like `resolve_torrents`, but exits unless exactly one torrent was selected
+pub fn resolve_torrent(info: &RequestInfo, selector: &TorrentSelector) -> TorrentInfoResponse {
+    let mut torrents = resolve_torrents(info, selector);
+
+    if torrents.len() > 1 {
+        eprintln!(
+            "The selection matched {} torrents, but this command only works on a single one:",
+            torrents.len()
+        );
+        for t in &torrents {
+            eprintln!("  {} {}", t.hash, t.name);
+        }
+        exit(1);
+    }
+
+    torrents.remove(0)
+}
+
+/// Resolves a single (prefix of a) hash
+pub fn resolve_hash(info: &RequestInfo, hash: &str) -> TorrentInfoResponse {
+    let selector = TorrentSelector {
+        hashes: vec![hash.to_string()],
+        all: false,
+        name: None,
+        glob: None,
+        category: None,
+        tag: None,
+        state: None,
+    };
+
+    resolve_torrent(info, &selector)
+}
+
+/// Selects the torrent with the hash starting with each prefix. Fails if a prefix matches
+/// no torrent or more than one.
+fn match_prefixes(
+    mut torrents: Vec<TorrentInfoResponse>,
+    prefixes: &[String],
+) -> Result<Vec<TorrentInfoResponse>, String> {
+    let mut selected_hashes: Vec<String> = Vec::new();
+
+    for prefix in prefixes {
+        let prefix = prefix.to_ascii_lowercase();
+        let matches: Vec<&TorrentInfoResponse> = torrents
+            .iter()
+            .filter(|t| t.hash.starts_with(&prefix))
+            .collect();
+
+        match matches.as_slice() {
+            [] => {
+                return Err(format!(
+                    "No selected torrent has a hash starting with {}",
+                    prefix
+                ))
+            }
+            [t] => selected_hashes.push(t.hash.clone()),
+            _ => {
+                let mut message = format!("The hash {} is ambiguous, it matches:", prefix);
+                for t in matches {
+                    message.push_str(&format!("\n  {} {}", t.hash, t.name));
+                }
+                return Err(message);
+            }
+        }
+    }
+
+    torrents.retain(|t| selected_hashes.contains(&t.hash));
+    Ok(torrents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_prefixes, TorrentInfoResponse};
+
+    fn torrent(hash: &str, name: &str) -> TorrentInfoResponse {
+        serde_json::from_value(serde_json::json!({
+            "hash": hash, "name": name, "progress": 1.0, "ratio": 0.0, "size": 0,
+            "state": "uploading", "added_on": 0, "tags": "", "category": "", "dlspeed": 0,
+            "upspeed": 0, "eta": 0, "num_seeds": 0, "num_leechs": 0, "ratio_limit": -2,
+            "seeding_time_limit": -2, "save_path": "", "seq_dl": false, "f_l_piece_prio": false,
+            "super_seeding": false, "force_start": false, "auto_tmm": false, "priority": 0,
+            "magnet_uri": ""
+        }))
+        .unwrap()
+    }
+
+    fn torrents() -> Vec<TorrentInfoResponse> {
+        vec![
+            torrent("8d3f1111", "first"),
+            torrent("8d3f2222", "second"),
+            torrent("c0ffee00", "third"),
+        ]
+    }
+
+    fn names(torrents: Vec<TorrentInfoResponse>) -> Vec<String> {
+        torrents.into_iter().map(|t| t.name).collect()
+    }
+
+    #[test]
+    fn unique_prefixes() {
+        let prefixes = ["C0F".to_string(), "8d3f2".to_string()];
+        let selected = match_prefixes(torrents(), &prefixes).unwrap();
+        assert_eq!(names(selected), ["second", "third"]);
+
+        let full = ["8d3f1111".to_string()];
+        assert_eq!(names(match_prefixes(torrents(), &full).unwrap()), ["first"]);
+    }
+
+    #[test]
+    fn ambiguous_prefix() {
+        let error = match_prefixes(torrents(), &["8d3f".to_string()]).unwrap_err();
+        assert!(error.contains("ambiguous"));
+        assert!(error.contains("8d3f1111 first"));
+        assert!(error.contains("8d3f2222 second"));
+    }
+
+    #[test]
+    fn missing_prefix() {
+        let prefixes = ["c0ffee".to_string(), "abcd".to_string()];
+        let error = match_prefixes(torrents(), &prefixes).unwrap_err();
+        assert_eq!(error, "No selected torrent has a hash starting with abcd");
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestInfo;
+
+use super::util::exit_if_expired;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TagResponse {
+    pub name: String,
+}
+
+pub fn list_tags(info: &RequestInfo) -> Vec<TagResponse> {
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/tags").unwrap())
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    let mut tags: Vec<TagResponse> = res.json().unwrap();
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+    tags
+}
+
+pub fn create_tags(info: &RequestInfo, tags: Vec<String>) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("tags", tags.join(","));
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/createTags").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    println!("Sent request to create {} tag(s).", tags.len());
+}
+
+pub fn delete_tags(info: &RequestInfo, tags: Vec<String>) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("tags", tags.join(","));
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/deleteTags").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    println!("Sent request to delete {} tag(s).", tags.len());
+}
@@ -1,27 +1,64 @@
-use std::{collections::HashMap, path::PathBuf, thread, time::Duration};
+use std::collections::HashMap;
 
-use humansize::{format_size, DECIMAL};
-use reqwest::blocking::multipart::Form;
-use serde::Deserialize;
-use url::Url;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    backend::util::{self, confirm, epoch_to_datetime, exit_if_expired, progress_render},
-    cli::TorrentSortingOptions,
+    backend::util::{self, confirm, exit_if_expired},
+    cli::{QueueDirection, TorrentFilterArgs, TorrentSortingOptions},
     config::RequestInfo,
 };
 
 use super::util::TorrentState;
 
-#[derive(Debug, Deserialize)]
-struct TorrentInfoResponse {
-    hash: String,
-    name: String,
-    progress: f64,
-    ratio: f64,
-    size: u64,
-    state: TorrentState,
-    added_on: i64,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TorrentInfoResponse {
+    pub hash: String,
+    pub name: String,
+    pub progress: f64,
+    pub ratio: f64,
+    pub size: u64,
+    pub state: TorrentState,
+    pub added_on: i64,
+    pub tags: String,
+    pub category: String,
+    pub dlspeed: u64,
+    pub upspeed: u64,
+    pub eta: u64,
+    pub num_seeds: u64,
+    pub num_leechs: u64,
+    pub ratio_limit: f64,
+    pub seeding_time_limit: i64,
+    // Only returned by newer versions of the api
+    #[serde(default = "global_limit")]
+    pub inactive_seeding_time_limit: i64,
+    pub save_path: String,
+    pub seq_dl: bool,
+    pub f_l_piece_prio: bool,
+    pub super_seeding: bool,
+    pub force_start: bool,
+    pub auto_tmm: bool,
+    // The position in the queue, 0 or -1 if the torrent is not queued
+    pub priority: i64,
+    pub magnet_uri: String,
+}
+
+impl TorrentInfoResponse {
+    /// The tags are returned as a single comma separated string
+    pub fn tag_list(&self) -> Vec<String> {
+        self.tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self.state, TorrentState::PausedUP | TorrentState::PausedDL)
+    }
+}
+
+fn global_limit() -> i64 {
+    -2
 }
 
 pub fn list_torrents(
@@ -29,96 +66,81 @@ pub fn list_torrents(
     sort_by: TorrentSortingOptions,
     reverse: bool,
     limit: Option<u32>,
-    interval: Option<u64>,
-) {
-    let sort_string = format!("{:?}", sort_by).to_ascii_lowercase();
-
+    filter: &TorrentFilterArgs,
+) -> Vec<TorrentInfoResponse> {
     let mut query: HashMap<&str, String> = HashMap::new();
-    query.insert("sort", sort_string.clone());
+    query.insert("sort", format!("{:?}", sort_by).to_ascii_lowercase());
 
     if reverse {
         query.insert("reverse", "true".to_string());
     }
 
-    if let Some(limit) = limit {
+    // The name filter is applied client side, so the limit has to be as well
+    if let (Some(limit), None) = (limit, &filter.name) {
         query.insert("limit", limit.to_string());
     }
 
-    let mut refresh_counter = 0;
-    loop {
-        let res = info
-            .client
-            .get(info.url.join("api/v2/torrents/info").unwrap())
-            .query(&query)
-            .send()
-            .unwrap();
-
-        exit_if_expired(&res);
-
-        let torrents: Vec<TorrentInfoResponse> = res.json().unwrap();
-
-        println!("\n");
-        for t in &torrents {
-            let added_on = epoch_to_datetime(t.added_on);
-
-            println!("   | {}\n   |", t.name);
-            println!("   |  > Hash: {}", t.hash);
-            println!(
-                "   |  > Progress: {:.2}% {}",
-                t.progress * 100.0,
-                progress_render(t.progress)
-            );
-            println!("   |  > Size: {}", format_size(t.size, DECIMAL));
-            println!("   |  > Aded on: {}", added_on);
-            println!("   |  > Ratio: {:.2}", t.ratio);
-            println!("   |  > State: {} ({:#})", t.state, t.state);
-
-            println!("\n")
-        }
+    if let Some(state) = filter.filter {
+        query.insert("filter", format!("{:?}", state).to_ascii_lowercase());
+    }
+
+    if let Some(category) = &filter.category {
+        query.insert("category", category.clone());
+    }
+
+    if let Some(tag) = &filter.tag {
+        query.insert("tag", tag.clone());
+    }
+
+    if !filter.hashes.is_empty() {
+        query.insert("hashes", filter.hashes.join("|"));
+    }
+
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/info").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    let mut torrents: Vec<TorrentInfoResponse> = res.json().unwrap();
+
+    if let Some(name) = &filter.name {
+        torrents.retain(|t| name.is_match(&t.name));
 
-        if let Some(interval) = interval {
-            println!(
-                "Found {} torrents, sorted by: {} {}\nRefreshed {} times, every {}ms",
-                torrents.len(),
-                sort_string,
-                match reverse {
-                    true => "(reversed)",
-                    false => "",
-                },
-                refresh_counter,
-                interval
-            );
-            thread::sleep(Duration::from_millis(interval));
-            refresh_counter += 1;
-
-            // Clear screen control char
-            print!("{}[2J", 27 as char);
-        } else {
-            println!(
-                "Found {} torrents, sorted by: {} {}",
-                torrents.len(),
-                sort_string,
-                match reverse {
-                    true => "(reversed)",
-                    false => "",
-                }
-            );
-            break;
+        if let Some(limit) = limit {
+            torrents.truncate(limit as usize);
         }
     }
+
+    torrents
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(unused)]
-struct TorrentFileResponse {
-    index: u64,
-    name: String,
-    piece_range: [u32; 2],
-    progress: f64,
-    size: u64,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TorrentFileResponse {
+    pub index: u64,
+    pub name: String,
+    pub piece_range: [u32; 2],
+    pub progress: f64,
+    pub size: u64,
+    pub priority: u8,
+    pub availability: f64,
 }
 
-pub fn torrent_content(info: &RequestInfo, hash: String) -> Option<()> {
+impl TorrentFileResponse {
+    pub fn priority_name(&self) -> String {
+        match self.priority {
+            0 => "Skip".to_string(),
+            1 => "Normal".to_string(),
+            6 => "High".to_string(),
+            7 => "Max".to_string(),
+            other => other.to_string(),
+        }
+    }
+}
+
+pub fn torrent_content(info: &RequestInfo, hash: String) -> Option<Vec<TorrentFileResponse>> {
     let mut query: HashMap<&str, String> = HashMap::new();
     query.insert("hash", hash);
 
@@ -130,78 +152,72 @@ pub fn torrent_content(info: &RequestInfo, hash: String) -> Option<()> {
         .unwrap();
     exit_if_expired(&content_res);
 
-    let json: Vec<TorrentFileResponse> = match content_res.json() {
-        Ok(v) => v,
-        Err(_) => return None,
-    };
-
-    for file in &json {
-        println!("\n\n   | {}\n   |", file.name);
-        println!(
-            "   |  > Progress: {:.2}% {}",
-            file.progress * 100.0,
-            progress_render(file.progress)
-        );
-        println!("   |  > Size: {}", format_size(file.size, DECIMAL));
-    }
+    content_res.json().ok()
+}
 
-    println!("\n\nTorrent contains {} files.", json.len());
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TorrentPropertiesResponse {
+    pub save_path: String,
+    pub creation_date: i64,
+    pub piece_size: i64,
+    pub comment: String,
+    pub created_by: String,
+    pub total_wasted: u64,
+    pub total_uploaded: u64,
+    pub total_uploaded_session: u64,
+    pub total_downloaded: u64,
+    pub total_downloaded_session: u64,
+    pub total_size: i64,
+    pub up_limit: i64,
+    pub dl_limit: i64,
+    pub time_elapsed: u64,
+    pub seeding_time: u64,
+    pub nb_connections: u64,
+    pub nb_connections_limit: i64,
+    pub share_ratio: f64,
+    pub addition_date: i64,
+    pub completion_date: i64,
+    pub last_seen: i64,
+    pub seeds: u64,
+    pub seeds_total: u64,
+    pub peers: u64,
+    pub peers_total: u64,
+    pub pieces_have: i64,
+    pub pieces_num: i64,
+}
 
-    Some(())
+/// The properties of a torrent, combined with the info that is only returned by the torrent list
+#[derive(Debug, Serialize)]
+pub struct TorrentDetails {
+    pub hash: String,
+    pub name: String,
+    pub ratio_limit: f64,
+    pub seeding_time_limit: i64,
+    #[serde(flatten)]
+    pub properties: TorrentPropertiesResponse,
 }
 
-pub fn add_torrent(info: &RequestInfo, url_or_path: String, pause: bool) {
-    let form = Form::new();
-
-    if let Ok(url) = Url::parse(&url_or_path) {
-        let form = form.text("urls", url.to_string());
-        let form = form.text("paused", pause.to_string());
-
-        let file_res = info
-            .client
-            .post(info.url.join("api/v2/torrents/add").unwrap())
-            .multipart(form)
-            .send()
-            .unwrap();
-        exit_if_expired(&file_res);
-
-        if file_res.text().unwrap() == "Ok." {
-            println!("Added url.")
-        } else {
-            eprintln!("Adding url failed.")
-        }
+pub fn torrent_details(info: &RequestInfo, torrent: TorrentInfoResponse) -> Option<TorrentDetails> {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", torrent.hash.clone());
 
-        return;
-    }
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/properties").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
 
-    if let Ok(path) = PathBuf::try_from(&url_or_path) {
-        let form = match form.file("torrents", &path) {
-            Ok(v) => v,
-            Err(e) => {
-                eprintln!("Failed reading file '{}': {}", &path.display(), e);
-                return;
-            }
-        };
-        let form = form.text("paused", pause.to_string());
-
-        let file_res = info
-            .client
-            .post(info.url.join("api/v2/torrents/add").unwrap())
-            .multipart(form)
-            .send()
-            .unwrap();
-        exit_if_expired(&file_res);
-
-        if file_res.text().unwrap() == "Ok." {
-            println!("Added torrent file.");
-        } else {
-            eprintln!("Adding torrent file failed.");
-        }
+    let properties: TorrentPropertiesResponse = res.json().ok()?;
 
-        return;
-    }
-
-    eprintln!("Provided data was not a valid url or a path");
+    Some(TorrentDetails {
+        hash: torrent.hash,
+        name: torrent.name,
+        ratio_limit: torrent.ratio_limit,
+        seeding_time_limit: torrent.seeding_time_limit,
+        properties,
+    })
 }
 
 pub fn delete_torrents(info: &RequestInfo, hashes: Vec<String>, delete_files: bool) {
@@ -236,66 +252,193 @@ pub fn delete_torrents(info: &RequestInfo, hashes: Vec<String>, delete_files: bo
     println!("Sent request to delete {} torrent(s).", hashes.len());
 }
 
-pub fn pause_torrent(info: &RequestInfo, hash: String) {
+pub fn pause_torrents(info: &RequestInfo, torrents: &[TorrentInfoResponse], all: bool) {
+    batch_action(info, "api/v2/torrents/pause", "pause", torrents, all);
+}
+
+pub fn resume_torrents(info: &RequestInfo, torrents: &[TorrentInfoResponse], all: bool) {
+    batch_action(info, "api/v2/torrents/resume", "resume", torrents, all);
+}
+
+pub fn recheck(info: &RequestInfo, torrents: &[TorrentInfoResponse], all: bool) {
+    batch_action(info, "api/v2/torrents/recheck", "recheck", torrents, all);
+}
+
+pub fn reannounce(info: &RequestInfo, torrents: &[TorrentInfoResponse], all: bool) {
+    batch_action(
+        info,
+        "api/v2/torrents/reannounce",
+        "reannounce",
+        torrents,
+        all,
+    );
+}
+
+pub fn move_in_queue(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+    direction: QueueDirection,
+) {
+    let (endpoint, action) = match direction {
+        QueueDirection::Top => ("api/v2/torrents/topPrio", "maximize the queue priority of"),
+        QueueDirection::Bottom => (
+            "api/v2/torrents/bottomPrio",
+            "minimize the queue priority of",
+        ),
+        QueueDirection::Up => (
+            "api/v2/torrents/increasePrio",
+            "increase the queue priority of",
+        ),
+        QueueDirection::Down => (
+            "api/v2/torrents/decreasePrio",
+            "decrease the queue priority of",
+        ),
+    };
+
+    batch_action(info, endpoint, action, torrents, all);
+}
+
+/// The value of the `hashes` parameter. The api accepts `all` instead of listing every hash
+pub fn hashes_param(torrents: &[TorrentInfoResponse], all: bool) -> String {
+    match all {
+        true => "all".to_string(),
+        false => torrents
+            .iter()
+            .map(|t| t.hash.as_str())
+            .collect::<Vec<_>>()
+            .join("|"),
+    }
+}
+
+/// Sends a single request for an action that accepts multiple hashes and reports the affected torrents
+fn batch_action(
+    info: &RequestInfo,
+    endpoint: &str,
+    action: &str,
+    torrents: &[TorrentInfoResponse],
+    all: bool,
+) {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hashes", hashes_param(torrents, all));
+
+    let res = info
+        .client
+        .post(info.url.join(endpoint).unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    if !res.status().is_success() {
+        eprintln!(
+            "Failed to {} {} torrent(s): {}",
+            action,
+            torrents.len(),
+            match res.status().as_u16() {
+                409 => "torrent queueing is disabled".to_string(),
+                _ => res.status().to_string(),
+            }
+        );
+        return;
+    }
+
+    println!("Sent request to {} {} torrent(s):", action, torrents.len());
+    for t in torrents {
+        println!("  {} {}", t.hash, t.name);
+    }
+}
+
+pub fn set_category(info: &RequestInfo, hashes: Vec<String>, category: String) {
     let mut formdata: HashMap<&str, String> = HashMap::new();
 
-    formdata.insert("hashes", hash);
+    formdata.insert("hashes", hashes.join("|"));
+    formdata.insert("category", category.clone());
 
     let res = info
         .client
-        .post(info.url.join("api/v2/torrents/pause").unwrap())
+        .post(info.url.join("api/v2/torrents/setCategory").unwrap())
         .form(&formdata)
         .send()
         .unwrap();
     exit_if_expired(&res);
 
-    println!("Sent request to pause torrent.");
+    if !res.status().is_success() {
+        eprintln!(
+            "Setting category failed, make sure the category {} exists.",
+            category
+        );
+        return;
+    }
+
+    println!(
+        "Sent request to set the category of {} torrent(s).",
+        hashes.len()
+    );
 }
 
-pub fn resume_torrent(info: &RequestInfo, hash: String) {
+pub fn add_tags(info: &RequestInfo, hashes: Vec<String>, tags: Vec<String>) {
     let mut formdata: HashMap<&str, String> = HashMap::new();
 
-    formdata.insert("hashes", hash);
+    formdata.insert("hashes", hashes.join("|"));
+    formdata.insert("tags", tags.join(","));
 
     let res = info
         .client
-        .post(info.url.join("api/v2/torrents/resume").unwrap())
+        .post(info.url.join("api/v2/torrents/addTags").unwrap())
         .form(&formdata)
         .send()
         .unwrap();
     exit_if_expired(&res);
 
-    println!("Sent request to resume torrent.");
+    println!(
+        "Sent request to add {} tag(s) to {} torrent(s).",
+        tags.len(),
+        hashes.len()
+    );
 }
 
-pub fn recheck(info: &RequestInfo, hash: String) {
+pub fn remove_tags(info: &RequestInfo, hashes: Vec<String>, tags: Vec<String>) {
     let mut formdata: HashMap<&str, String> = HashMap::new();
 
-    formdata.insert("hashes", hash);
+    formdata.insert("hashes", hashes.join("|"));
+    formdata.insert("tags", tags.join(","));
 
     let res = info
         .client
-        .post(info.url.join("api/v2/torrents/recheck").unwrap())
+        .post(info.url.join("api/v2/torrents/removeTags").unwrap())
         .form(&formdata)
         .send()
         .unwrap();
     exit_if_expired(&res);
 
-    println!("Sent request to recheck torrent.");
+    println!(
+        "Sent request to remove {} tag(s) from {} torrent(s).",
+        tags.len(),
+        hashes.len()
+    );
 }
 
-pub fn reannounce(info: &RequestInfo, hash: String) {
+pub fn rename_torrent(info: &RequestInfo, torrent: &TorrentInfoResponse, name: String) {
     let mut formdata: HashMap<&str, String> = HashMap::new();
 
-    formdata.insert("hashes", hash);
+    formdata.insert("hash", torrent.hash.clone());
+    formdata.insert("name", name.clone());
 
     let res = info
         .client
-        .post(info.url.join("api/v2/torrents/reannounce").unwrap())
+        .post(info.url.join("api/v2/torrents/rename").unwrap())
         .form(&formdata)
         .send()
         .unwrap();
     exit_if_expired(&res);
 
-    println!("Sent request to reannounce torrent.");
+    match res.status().is_success() {
+        true => println!("Renamed {} to {}", torrent.name, name),
+        false => eprintln!(
+            "Renaming {} failed, make sure the name is not empty",
+            torrent.name
+        ),
+    }
 }
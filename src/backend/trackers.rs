@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    cli::{TorrentFilterArgs, TorrentSortingOptions},
+    config::RequestInfo,
+};
+
+use super::{list_torrents, util::exit_if_expired, TorrentInfoResponse};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackerResponse {
+    pub url: String,
+    pub status: u8,
+    #[serde(deserialize_with = "deserialize_tier")]
+    pub tier: i64,
+    pub num_peers: i64,
+    pub num_seeds: i64,
+    pub num_leeches: i64,
+    pub num_downloaded: i64,
+    pub msg: String,
+}
+
+impl TrackerResponse {
+    pub fn status_name(&self) -> &'static str {
+        match self.status {
+            0 => "Disabled",
+            1 => "Not contacted",
+            2 => "Working",
+            3 => "Updating",
+            4 => "Not working",
+            _ => "Unknown",
+        }
+    }
+
+    /// DHT, PeX and LSD are listed as trackers as well, but can't be edited
+    pub fn is_pseudo_tracker(&self) -> bool {
+        self.url.starts_with("** [")
+    }
+}
+
+/// qBittorrent before 4.4 sends an empty string as the tier of DHT, PeX and LSD
+fn deserialize_tier<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tier {
+        Number(i64),
+        Text(String),
+    }
+
+    Ok(match Tier::deserialize(deserializer)? {
+        Tier::Number(tier) => tier,
+        Tier::Text(tier) => tier.parse().unwrap_or(-1),
+    })
+}
+
+pub fn list_trackers(info: &RequestInfo, hash: &str) -> Option<Vec<TrackerResponse>> {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", hash.to_string());
+
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/trackers").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.json().ok()
+}
+
+pub fn add_trackers(info: &RequestInfo, torrents: &[TorrentInfoResponse], urls: Vec<String>) {
+    for t in torrents {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hash", t.hash.clone());
+        formdata.insert("urls", urls.join("\n"));
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/addTrackers").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        match res.status().is_success() {
+            true => println!("Added {} tracker(s) to {}", urls.len(), t.name),
+            false => eprintln!("Adding trackers to {} failed: {}", t.name, res.status()),
+        }
+    }
+}
+
+pub fn edit_tracker(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    orig_url: String,
+    new_url: String,
+) {
+    for t in torrents {
+        match send_edit_tracker(info, &t.hash, &orig_url, &new_url) {
+            true => println!("Replaced tracker of {}", t.name),
+            false => eprintln!(
+                "Replacing tracker of {} failed, make sure the torrent has the tracker {} and not already {}",
+                t.name, orig_url, new_url
+            ),
+        }
+    }
+}
+
+fn send_edit_tracker(info: &RequestInfo, hash: &str, orig_url: &str, new_url: &str) -> bool {
+    let mut formdata: HashMap<&str, String> = HashMap::new();
+
+    formdata.insert("hash", hash.to_string());
+    formdata.insert("origUrl", orig_url.to_string());
+    formdata.insert("newUrl", new_url.to_string());
+
+    let res = info
+        .client
+        .post(info.url.join("api/v2/torrents/editTracker").unwrap())
+        .form(&formdata)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.status().is_success()
+}
+
+pub fn remove_trackers(info: &RequestInfo, torrents: &[TorrentInfoResponse], urls: Vec<String>) {
+    for t in torrents {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hash", t.hash.clone());
+        formdata.insert("urls", urls.join("|"));
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/removeTrackers").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+
+        match res.status().is_success() {
+            true => println!("Removed {} tracker(s) from {}", urls.len(), t.name),
+            false => eprintln!(
+                "Removing trackers from {} failed, make sure the torrent has all of them",
+                t.name
+            ),
+        }
+    }
+}
+
+/// Rewrites the announce urls matching the pattern on every torrent of the instance
+pub fn replace_trackers(info: &RequestInfo, pattern: &Regex, replacement: &str, dry_run: bool) {
+    let torrents = list_torrents(
+        info,
+        TorrentSortingOptions::Name,
+        false,
+        None,
+        &TorrentFilterArgs::default(),
+    );
+
+    let mut replaced = 0;
+    let mut failed = 0;
+    let mut affected_torrents = 0;
+    let mut unchecked_torrents = 0;
+
+    for t in &torrents {
+        let Some(trackers) = list_trackers(info, &t.hash) else {
+            eprintln!("Failed fetching the trackers of {}", t.name);
+            unchecked_torrents += 1;
+            continue;
+        };
+
+        let changes: Vec<(String, String)> = trackers
+            .iter()
+            .filter(|tracker| !tracker.is_pseudo_tracker())
+            .map(|tracker| {
+                let new_url = pattern.replace_all(&tracker.url, replacement);
+                (tracker.url.clone(), new_url.to_string())
+            })
+            .filter(|(old_url, new_url)| old_url != new_url)
+            .collect();
+
+        if changes.is_empty() {
+            continue;
+        }
+
+        affected_torrents += 1;
+        println!("{} ({})", t.name, t.hash);
+
+        for (old_url, new_url) in changes {
+            let result = if dry_run {
+                "dry run"
+            } else if send_edit_tracker(info, &t.hash, &old_url, &new_url) {
+                replaced += 1;
+                "ok"
+            } else {
+                failed += 1;
+                "failed"
+            };
+
+            println!("  {} -> {} [{}]", old_url, new_url, result);
+        }
+    }
+
+    if dry_run {
+        println!(
+            "\nDry run, nothing was changed. {} of {} torrent(s) would be affected.",
+            affected_torrents,
+            torrents.len()
+        );
+    } else {
+        println!(
+            "\nReplaced {} tracker(s) on {} torrent(s), {} failed.",
+            replaced, affected_torrents, failed
+        );
+    }
+
+    if unchecked_torrents > 0 {
+        eprintln!(
+            "The trackers of {} torrent(s) could not be fetched, they were not checked.",
+            unchecked_torrents
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TrackerResponse;
+
+    fn tracker_with_tier(tier: &str) -> TrackerResponse {
+        serde_json::from_str(&format!(
+            r#"{{"url":"** [DHT] **","status":2,"tier":{},"num_peers":5,"num_seeds":0,"num_leeches":0,"num_downloaded":-1,"msg":""}}"#,
+            tier
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn numeric_tier() {
+        assert_eq!(tracker_with_tier("-1").tier, -1);
+        assert_eq!(tracker_with_tier("2").tier, 2);
+    }
+
+    #[test]
+    fn empty_tier_of_old_versions() {
+        assert_eq!(tracker_with_tier(r#""""#).tier, -1);
+        assert_eq!(tracker_with_tier(r#""3""#).tier, 3);
+    }
+}
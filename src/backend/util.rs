@@ -1,13 +1,17 @@
 use std::{
     io::{self, BufRead, Write},
     process::exit,
+    time::Duration,
 };
 
-use chrono::NaiveDateTime;
+use regex::Regex;
 use reqwest::blocking::Response;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+/// How long to wait between requests when waiting for qBittorrent to finish something
+pub const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TorrentState {
     Error,
@@ -85,22 +89,6 @@ impl std::fmt::Display for TorrentState {
     }
 }
 
-pub fn progress_render(progress: f64) -> String {
-    let progress = (progress * 10.0) as u32;
-    let mut s = "<".to_string();
-
-    for _ in 0..progress {
-        s.push('#');
-    }
-
-    for _ in progress..10 {
-        s.push('_');
-    }
-    s.push('>');
-
-    return s;
-}
-
 #[allow(unused)]
 pub enum DefaultChoice {
     Yes,
@@ -141,6 +129,56 @@ pub fn exit_if_expired(res: &Response) {
     }
 }
 
-pub fn epoch_to_datetime(epoch: i64) -> NaiveDateTime {
-    NaiveDateTime::from_timestamp_opt(epoch, 0).unwrap()
+/// Endpoints that were added in newer versions of the api return 404 on older servers
+pub fn exit_if_unsupported(res: &Response) {
+    if res.status() == 404 {
+        eprintln!("This command is not supported by the qBittorrent version of the server.");
+        exit(1);
+    }
+}
+
+/// Converts a glob, where `*` matches any characters and `?` a single one, into an anchored regex
+pub fn glob_to_regex(glob: &str) -> Regex {
+    let mut pattern = "^".to_string();
+
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    pattern.push('$');
+
+    Regex::new(&pattern).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_to_regex;
+
+    #[test]
+    fn glob_wildcards() {
+        let re = glob_to_regex("*.mkv");
+        assert!(re.is_match("movie.mkv"));
+        assert!(re.is_match("sample/movie.mkv"));
+        assert!(!re.is_match("movie.mkv.part"));
+
+        let re = glob_to_regex("?.txt");
+        assert!(re.is_match("a.txt"));
+        assert!(!re.is_match("ab.txt"));
+        assert!(!re.is_match(".txt"));
+    }
+
+    #[test]
+    fn glob_escapes_regex_characters() {
+        let re = glob_to_regex("a+b (1).txt");
+        assert!(re.is_match("a+b (1).txt"));
+        assert!(!re.is_match("aab 1.txt"));
+        assert!(!re.is_match("a+b (1)xtxt"));
+
+        let re = glob_to_regex("[sample]*");
+        assert!(re.is_match("[sample] a.mkv"));
+        assert!(!re.is_match("s"));
+    }
 }
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestInfo;
+
+use super::{
+    util::{exit_if_expired, exit_if_unsupported},
+    TorrentInfoResponse,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebSeedResponse {
+    pub url: String,
+}
+
+pub fn list_webseeds(info: &RequestInfo, hash: &str) -> Option<Vec<WebSeedResponse>> {
+    let mut query: HashMap<&str, String> = HashMap::new();
+    query.insert("hash", hash.to_string());
+
+    let res = info
+        .client
+        .get(info.url.join("api/v2/torrents/webseeds").unwrap())
+        .query(&query)
+        .send()
+        .unwrap();
+    exit_if_expired(&res);
+
+    res.json().ok()
+}
+
+pub fn add_webseeds(info: &RequestInfo, torrents: &[TorrentInfoResponse], urls: Vec<String>) {
+    for t in torrents {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hash", t.hash.clone());
+        formdata.insert("urls", urls.join("|"));
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/addWebSeeds").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+        exit_if_unsupported(&res);
+
+        match res.status().is_success() {
+            true => println!("Added {} web seed(s) to {}", urls.len(), t.name),
+            false => eprintln!(
+                "Adding web seeds to {} failed, make sure the urls are valid",
+                t.name
+            ),
+        }
+    }
+}
+
+pub fn edit_webseed(
+    info: &RequestInfo,
+    torrents: &[TorrentInfoResponse],
+    orig_url: String,
+    new_url: String,
+) {
+    for t in torrents {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hash", t.hash.clone());
+        formdata.insert("origUrl", orig_url.clone());
+        formdata.insert("newUrl", new_url.clone());
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/editWebSeed").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+        exit_if_unsupported(&res);
+
+        match res.status().is_success() {
+            true => println!("Replaced web seed of {}", t.name),
+            false => eprintln!(
+                "Replacing web seed of {} failed, make sure the torrent has the web seed {} and not already {}",
+                t.name, orig_url, new_url
+            ),
+        }
+    }
+}
+
+pub fn remove_webseeds(info: &RequestInfo, torrents: &[TorrentInfoResponse], urls: Vec<String>) {
+    for t in torrents {
+        let mut formdata: HashMap<&str, String> = HashMap::new();
+
+        formdata.insert("hash", t.hash.clone());
+        formdata.insert("urls", urls.join("|"));
+
+        let res = info
+            .client
+            .post(info.url.join("api/v2/torrents/removeWebSeeds").unwrap())
+            .form(&formdata)
+            .send()
+            .unwrap();
+        exit_if_expired(&res);
+        exit_if_unsupported(&res);
+
+        match res.status().is_success() {
+            true => println!("Removed {} web seed(s) from {}", urls.len(), t.name),
+            false => eprintln!(
+                "Removing web seeds from {} failed: {}",
+                t.name,
+                res.status()
+            ),
+        }
+    }
+}
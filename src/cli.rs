@@ -1,4 +1,7 @@
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand, ValueEnum};
+use regex::Regex;
 use url::Url;
 
 #[derive(Debug, Clone, Parser)]
@@ -6,6 +9,18 @@ use url::Url;
 pub struct BaseCommand {
     #[command(subcommand)]
     pub commands: Commands,
+
+    /// The format used when printing lists (e.g. of torrents or logs)
+    #[arg(short, long, global = true, default_value = "human")]
+    pub output: OutputFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Human,
+    Json,
+    Csv,
+    Tsv,
 }
 
 #[derive(Debug, Clone, Subcommand)]
@@ -15,6 +30,10 @@ pub enum Commands {
     Auth(Auth),
     Torrent(Torrent),
     Global(Global),
+    Category(Category),
+    Tag(Tag),
+    Tracker(Tracker),
+    Backup(Backup),
 }
 
 /// Control authentication for different urls
@@ -71,6 +90,13 @@ pub enum GlobalCommands {
     /// Displays the logs
     Log,
 
+    /// Bans one or multiple peers (host:port) permanently
+    BanPeers {
+        /// The peers to ban (host:port)
+        #[arg(required = true)]
+        peers: Vec<String>,
+    },
+
     /// Displays or toggles alternative speed limits
     AltSpeed {
         #[arg(short, long)]
@@ -88,60 +114,672 @@ pub struct Torrent {
 #[derive(Subcommand, Clone, Debug)]
 pub enum TorrentCommands {
     /// List all torrents
-    List {
-        /// Sort the torrents by this value
+    List(TorrentListArgs),
+    /// Show the contents of a specific torrent
+    Content {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Show which pieces of a specific torrent are downloaded
+    Pieces {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// Also show the pieces of every file (only in the human readable output)
         #[arg(short, long)]
-        sort: Option<TorrentSortingOptions>,
+        files: bool,
+    },
+    /// Show the properties of a specific torrent, such as its save path, comment or piece size
+    Info {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// List the trackers of a specific torrent
+    Trackers {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Add one or multiple trackers to one or multiple torrents
+    AddTracker {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The announce urls of the trackers
+        #[arg(short, long = "url", required = true)]
+        urls: Vec<String>,
+    },
+    /// Replace a tracker of one or multiple torrents
+    EditTracker {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The announce url to replace
+        #[arg(long)]
+        old: String,
+
+        /// The new announce url
+        #[arg(long)]
+        new: String,
+    },
+    /// Remove one or multiple trackers from one or multiple torrents
+    RemoveTracker {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The announce urls of the trackers
+        #[arg(short, long = "url", required = true)]
+        urls: Vec<String>,
+    },
+    /// List the web seeds (http sources) of a specific torrent
+    Webseeds {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Add one or multiple web seeds to one or multiple torrents
+    AddWebseed {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The urls of the web seeds
+        #[arg(short, long = "url", required = true)]
+        urls: Vec<String>,
+    },
+    /// Replace a web seed of one or multiple torrents
+    EditWebseed {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The url to replace
+        #[arg(long)]
+        old: String,
+
+        /// The new url
+        #[arg(long)]
+        new: String,
+    },
+    /// Remove one or multiple web seeds from one or multiple torrents
+    RemoveWebseed {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The urls of the web seeds
+        #[arg(short, long = "url", required = true)]
+        urls: Vec<String>,
+    },
+    /// List the connected peers of a specific torrent
+    Peers {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Connect one or multiple torrents to specific peers
+    AddPeers {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The peers to add (host:port)
+        #[arg(short, long = "peer", required = true)]
+        peers: Vec<String>,
+    },
+    Files(Files),
+    /// Move one or multiple torrents in the download queue
+    Queue {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// Where to move the torrents in the queue
+        #[arg(long, value_enum)]
+        direction: QueueDirection,
+    },
+    /// Turn options like sequential download or force start on or off for one or multiple torrents
+    Set {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        #[command(flatten)]
+        flags: TorrentFlags,
+    },
+    /// Set the speed limits of one or multiple torrents, or show them if no limit is given
+    Limit {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// Download limit, e.g. 5MiB/s or 500KB/s (0 or unlimited to remove the limit)
+        #[arg(long, value_parser = parse_rate)]
+        dl: Option<u64>,
+
+        /// Upload limit, e.g. 5MiB/s or 500KB/s (0 or unlimited to remove the limit)
+        #[arg(long, value_parser = parse_rate)]
+        ul: Option<u64>,
+    },
+    /// Set the share limits of one or multiple torrents, or show them if no limit is given
+    ShareLimit {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// Stop seeding at this share ratio (global to use the global limit, unlimited for none)
+        #[arg(long, value_parser = parse_ratio_limit)]
+        ratio: Option<f64>,
 
-        /// Reverse the display order
+        /// Stop seeding after this many minutes (global to use the global limit, unlimited for none)
+        #[arg(long, value_parser = parse_time_limit)]
+        seeding_time: Option<i64>,
+
+        /// Stop seeding after being inactive for this many minutes (global to use the global limit, unlimited for none)
+        #[arg(long, value_parser = parse_time_limit)]
+        inactive_seeding_time: Option<i64>,
+    },
+    /// Move the data of one or multiple torrents to a new location
+    Move {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The directory to move the torrents to
+        #[arg(long = "to", value_name = "LOCATION")]
+        location: String,
+
+        /// Wait until the torrents have finished moving
         #[arg(short, long)]
-        reverse: bool,
+        wait: bool,
+    },
+    /// Change the save path of one or multiple torrents (requires qBittorrent 4.4 or newer)
+    SetSavePath {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The new save path
+        #[arg(long = "to", value_name = "PATH")]
+        path: String,
 
-        /// Limit the number of displayed torrents
+        /// Wait until the torrents have finished moving
         #[arg(short, long)]
-        limit: Option<u32>,
+        wait: bool,
+    },
+    /// Change the incomplete download path of one or multiple torrents (requires qBittorrent 4.4 or newer)
+    SetDownloadPath {
+        #[command(flatten)]
+        selector: TorrentSelector,
 
-        /// Refresh the screen every X milliseconds
+        /// The new download path
+        #[arg(long = "to", value_name = "PATH")]
+        path: String,
+
+        /// Wait until the torrents have finished moving
         #[arg(short, long)]
-        interval: Option<u64>,
+        wait: bool,
     },
-    /// Show the contents of a specific torrent
-    Content {
-        /// The hash of the torrent
+    /// Rename a torrent (this doesn't rename any files)
+    Rename {
+        /// The hash of the torrent (a unique prefix is enough)
         hash: String,
+
+        /// The new name
+        name: String,
     },
-    /// Add a new torrent from a file or URL
+    /// Rename a file inside of a torrent
+    RenameFile {
+        /// The hash of the torrent (a unique prefix is enough)
+        hash: String,
+
+        /// The current path of the file inside the torrent, or a regex with --regex
+        old_path: String,
+
+        /// The new path of the file, or the replacement with --regex (capture groups can be used as $1, $2, ...)
+        new_path: String,
+
+        /// Rename all files whose path matches the regex given as OLD_PATH
+        #[arg(short, long)]
+        regex: bool,
+
+        /// Only show what would be renamed
+        #[arg(short, long, requires = "regex")]
+        dry_run: bool,
+    },
+    /// Rename a folder inside of a torrent
+    RenameFolder {
+        /// The hash of the torrent (a unique prefix is enough)
+        hash: String,
+
+        /// The current path of the folder inside the torrent
+        old_path: String,
+
+        /// The new path of the folder
+        new_path: String,
+    },
+    /// Add one or multiple torrents from files or URLs
     Add {
-        /// A url (magnet) or a path to a torrent file
-        url_or_path: String,
+        /// Urls (magnet), paths to torrent files or directories containing torrent files.
+        /// Pass - to read urls from stdin (one per line)
+        #[arg(required = true)]
+        inputs: Vec<String>,
 
-        /// pause the torrent upon creation (don't download immediately)
+        #[command(flatten)]
+        options: TorrentAddOptions,
+    },
+    /// Save the .torrent files of one or multiple torrents
+    Export {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The directory to save the files in, named after the torrents
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// Move one or multiple torrents to another stored instance, keeping their save path, category and tags
+    Migrate {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The instance to take the torrents from. Defaults to the default url
+        #[arg(long)]
+        from: Option<Url>,
+
+        /// The instance to add the torrents to
+        #[arg(long)]
+        to: Url,
+
+        /// Remove the torrents from the source once the destination has finished checking them (the files are kept)
         #[arg(short, long)]
-        pause: bool,
+        delete: bool,
     },
     /// Delete one or multiple torrents (and optionally their files on disk)
     Delete {
-        /// The hashs of the torrents to be deleted
-        hashes: Vec<String>,
+        #[command(flatten)]
+        selector: TorrentSelector,
 
         /// DANGER! This will also delete the downloaded files from the filesystem
         #[arg(short, long)]
         delete_files: bool,
     },
-    /// Pasue a torrent
+    /// Pause one or multiple torrents
     Pause {
-        /// The hash of the torrent
-        hash: String,
+        #[command(flatten)]
+        selector: TorrentSelector,
     },
-    /// Resume a torrent
+    /// Resume one or multiple torrents
     Resume {
-        /// The hash of the torrent
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Forces the recheck of one or multiple torrents
+    Recheck {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Forces the reannounce of one or multiple torrents
+    Reannounce {
+        #[command(flatten)]
+        selector: TorrentSelector,
+    },
+    /// Set the category of one or multiple torrents
+    SetCategory {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The category to assign. Pass an empty string ("") to remove the category
+        #[arg(long = "to", value_name = "CATEGORY")]
+        new_category: String,
+    },
+    /// Add one or multiple tags to one or multiple torrents
+    AddTags {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The tags to add (comma separated)
+        #[arg(short, long, required = true, value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+    /// Remove one or multiple tags from one or multiple torrents
+    RemoveTags {
+        #[command(flatten)]
+        selector: TorrentSelector,
+
+        /// The tags to remove (comma separated)
+        #[arg(short, long, required = true, value_delimiter = ',')]
+        tags: Vec<String>,
+    },
+}
+
+/// Manage categories, such as creating or removing them
+#[derive(Debug, Clone, Args)]
+pub struct Category {
+    #[command(subcommand)]
+    pub commands: CategoryCommands,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum CategoryCommands {
+    /// List all categories
+    List,
+    /// Create a new category
+    Create {
+        /// The name of the category
+        name: String,
+
+        /// The save path of the category (uses the default save path if not set)
+        #[arg(short, long)]
+        save_path: Option<String>,
+    },
+    /// Change the save path of an existing category
+    Edit {
+        /// The name of the category
+        name: String,
+
+        /// The new save path. Pass an empty string ("") to use the default save path
+        save_path: String,
+    },
+    /// Remove one or multiple categories
+    Remove {
+        /// The names of the categories to be removed
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct TorrentAddOptions {
+    /// pause the torrent upon creation (don't download immediately)
+    #[arg(short, long)]
+    pub pause: bool,
+
+    /// Download the torrent to this directory
+    #[arg(long)]
+    pub savepath: Option<String>,
+
+    /// Assign the torrent to this category
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Add these tags to the torrent (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Rename the torrent
+    #[arg(long)]
+    pub rename: Option<String>,
+
+    /// Skip hash checking of already existing files
+    #[arg(long)]
+    pub skip_checking: bool,
+
+    /// Download the pieces in sequential order
+    #[arg(long)]
+    pub sequential: bool,
+
+    /// Prioritize downloading the first and last pieces of each file
+    #[arg(long)]
+    pub first_last_piece: bool,
+
+    /// How the files of the torrent are laid out in the save path
+    #[arg(long)]
+    pub content_layout: Option<ContentLayout>,
+
+    /// Use automatic torrent management (the save path is determined by the category)
+    #[arg(long)]
+    pub auto_tmm: bool,
+
+    /// Upload limit, e.g. 500KiB/s or 2MB/s
+    #[arg(long, value_parser = parse_rate)]
+    pub up_limit: Option<u64>,
+
+    /// Download limit, e.g. 500KiB/s or 2MB/s
+    #[arg(long, value_parser = parse_rate)]
+    pub dl_limit: Option<u64>,
+
+    /// Stop seeding once this share ratio is reached
+    #[arg(long)]
+    pub ratio_limit: Option<f64>,
+
+    /// Stop seeding after this many minutes
+    #[arg(long)]
+    pub seeding_time_limit: Option<i64>,
+
+    /// Cookie sent to download the torrent file from a url
+    #[arg(long)]
+    pub cookie: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum ContentLayout {
+    /// Keep the layout of the torrent
+    Original,
+    /// Always create a root folder
+    Subfolder,
+    /// Never create a root folder
+    NoSubfolder,
+}
+
+/// Control the files inside of a torrent
+#[derive(Debug, Clone, Args)]
+pub struct Files {
+    #[command(subcommand)]
+    pub commands: FilesCommands,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum FilesCommands {
+    /// Set the download priority of one or multiple files (use skip to not download them)
+    SetPriority {
+        /// The hash of the torrent (a unique prefix is enough)
         hash: String,
+
+        /// The indices of the files (as shown by torrent content) or globs matching their paths, e.g. "*sample*"
+        #[arg(required = true)]
+        files: Vec<String>,
+
+        /// The new priority
+        #[arg(short, long)]
+        priority: FilePriority,
     },
-    /// Forces the recheck of a torrent
-    Recheck { hash: String },
-    /// Forces the reannounce of a torrent
-    Reannounce { hash: String },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum FilePriority {
+    Skip,
+    Normal,
+    High,
+    Max,
+}
+
+impl FilePriority {
+    pub fn api_value(&self) -> u8 {
+        match self {
+            FilePriority::Skip => 0,
+            FilePriority::Normal => 1,
+            FilePriority::High => 6,
+            FilePriority::Max => 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = true)]
+pub struct TorrentFlags {
+    /// Download the pieces in sequential order
+    #[arg(long)]
+    pub sequential: Option<Switch>,
+
+    /// Prioritize downloading the first and last pieces of each file
+    #[arg(long)]
+    pub first_last_piece: Option<Switch>,
+
+    /// Only send pieces to peers that don't have them from other peers yet (when seeding)
+    #[arg(long)]
+    pub super_seeding: Option<Switch>,
+
+    /// Start the torrent regardless of the queue limits
+    #[arg(long)]
+    pub force_start: Option<Switch>,
+
+    /// Use automatic torrent management (the save path is determined by the category)
+    #[arg(long)]
+    pub auto_tmm: Option<Switch>,
+}
+
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub enum QueueDirection {
+    /// Move to the top of the queue
+    Top,
+    /// Move to the bottom of the queue
+    Bottom,
+    /// Move up by one position
+    Up,
+    /// Move down by one position
+    Down,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Switch {
+    On,
+    Off,
+}
+
+impl Switch {
+    pub fn is_on(&self) -> bool {
+        *self == Switch::On
+    }
+}
+
+/// Selects torrents by (a prefix of) their hash or by their properties. All given criteria must match
+#[derive(Debug, Clone, Args)]
+#[group(required = true, multiple = true)]
+pub struct TorrentSelector {
+    /// The hashes of the torrents. A unique prefix of the hash is enough
+    pub hashes: Vec<String>,
+
+    /// Select all torrents
+    #[arg(long)]
+    pub all: bool,
+
+    /// Select torrents whose name matches this regex
+    #[arg(long, value_parser = Regex::new)]
+    pub name: Option<Regex>,
+
+    /// Select torrents whose whole name matches this glob, e.g. "*.iso"
+    #[arg(long)]
+    pub glob: Option<String>,
+
+    /// Select torrents in this category
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Select torrents with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Select torrents in this state
+    #[arg(long)]
+    pub state: Option<TorrentFilter>,
+}
+
+impl TorrentSelector {
+    /// Whether every torrent of the instance is selected
+    pub fn is_all(&self) -> bool {
+        self.all
+            && self.hashes.is_empty()
+            && self.name.is_none()
+            && self.glob.is_none()
+            && self.category.is_none()
+            && self.tag.is_none()
+            && self.state.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct TorrentListArgs {
+    /// Sort the torrents by this value
+    #[arg(short, long)]
+    pub sort: Option<TorrentSortingOptions>,
+
+    /// Reverse the display order
+    #[arg(short, long)]
+    pub reverse: bool,
+
+    /// Limit the number of displayed torrents
+    #[arg(short, long)]
+    pub limit: Option<u32>,
+
+    /// Refresh the screen every X milliseconds
+    #[arg(short, long)]
+    pub interval: Option<u64>,
+
+    /// Display the torrents as a compact table (one line per torrent)
+    #[arg(short = 'T', long)]
+    pub table: bool,
+
+    /// The columns to show in the table (comma separated). Implies --table
+    #[arg(long, value_delimiter = ',')]
+    pub columns: Option<Vec<TorrentColumn>>,
+
+    #[command(flatten)]
+    pub filter: TorrentFilterArgs,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TorrentColumn {
+    Name,
+    Hash,
+    Size,
+    Progress,
+    Dlspeed,
+    Upspeed,
+    Eta,
+    Ratio,
+    State,
+    Seeds,
+    Peers,
+    Category,
+    Tags,
+    AddedOn,
+    Priority,
+}
+
+impl TorrentColumn {
+    pub const DEFAULT: &'static [TorrentColumn] = &[
+        TorrentColumn::Name,
+        TorrentColumn::Size,
+        TorrentColumn::Progress,
+        TorrentColumn::Dlspeed,
+        TorrentColumn::Upspeed,
+        TorrentColumn::Eta,
+        TorrentColumn::Ratio,
+        TorrentColumn::State,
+    ];
+}
+
+#[derive(Debug, Clone, Default, Args)]
+pub struct TorrentFilterArgs {
+    /// Only show torrents in this state
+    #[arg(short, long)]
+    pub filter: Option<TorrentFilter>,
+
+    /// Only show torrents in this category. Pass an empty string ("") for uncategorized torrents
+    #[arg(short, long)]
+    pub category: Option<String>,
+
+    /// Only show torrents with this tag. Pass an empty string ("") for untagged torrents
+    #[arg(short, long)]
+    pub tag: Option<String>,
+
+    /// Only show torrents with these hashes (comma separated)
+    #[arg(long, value_delimiter = ',')]
+    pub hashes: Vec<String>,
+
+    /// Only show torrents whose name matches this regex
+    #[arg(short, long, value_parser = Regex::new)]
+    pub name: Option<Regex>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum TorrentFilter {
+    All,
+    Downloading,
+    Seeding,
+    Completed,
+    Paused,
+    Active,
+    Inactive,
+    Stalled,
+    Errored,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -154,4 +792,191 @@ pub enum TorrentSortingOptions {
     Ratio,
     State,
     Added_On,
+    Priority,
+}
+
+/// Manage tags, such as creating or deleting them
+#[derive(Debug, Clone, Args)]
+pub struct Tag {
+    #[command(subcommand)]
+    pub commands: TagCommands,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum TagCommands {
+    /// List all tags
+    List,
+    /// Create one or multiple tags
+    Create {
+        /// The names of the tags
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Delete one or multiple tags (removing them from all torrents)
+    Delete {
+        /// The names of the tags
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+}
+
+/// Save all torrents of an instance to a file and add them again later
+#[derive(Debug, Clone, Args)]
+pub struct Backup {
+    #[command(subcommand)]
+    pub commands: BackupCommands,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum BackupCommands {
+    /// Save the .torrent files (or magnet links), save paths, categories, tags, limits and options of all torrents
+    Create {
+        /// The file to write the backup to
+        file: PathBuf,
+    },
+    /// Add the torrents of a backup that are not already added
+    Restore {
+        /// The backup file
+        file: PathBuf,
+    },
+}
+
+/// Manage the trackers of all torrents at once
+#[derive(Debug, Clone, Args)]
+pub struct Tracker {
+    #[command(subcommand)]
+    pub commands: TrackerCommands,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum TrackerCommands {
+    /// Rewrite the announce urls of all torrents, e.g. when a tracker changed its domain
+    Replace {
+        /// Regex matched against every announce url
+        #[arg(value_parser = Regex::new)]
+        pattern: Regex,
+
+        /// Replacement for the matched part. Capture groups can be used as $1, $2, ...
+        replacement: String,
+
+        /// Only show what would be replaced
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+}
+
+/// Parses a transfer rate like `5MiB/s`, `500 KB/s` or `1024` (bytes). SI units are powers of 1000,
+/// IEC units (KiB, MiB, ...) powers of 1024. `unlimited` is the same as 0.
+pub fn parse_rate(rate: &str) -> Result<u64, String> {
+    let rate = rate.trim();
+    if rate.eq_ignore_ascii_case("unlimited") {
+        return Ok(0);
+    }
+
+    let without_suffix = rate.strip_suffix("/s").unwrap_or(rate);
+    let split = without_suffix
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(without_suffix.len());
+    let (number, unit) = without_suffix.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid rate: {}", rate))?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1000,
+        "kib" => 1024,
+        "m" | "mb" => 1_000_000,
+        "mib" => 1024 * 1024,
+        "g" | "gb" => 1_000_000_000,
+        "gib" => 1024 * 1024 * 1024,
+        unit => return Err(format!("Unknown unit: {}", unit)),
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+// The api uses -2 for the global limit and -1 for no limit
+fn parse_limit_keyword(limit: &str) -> Option<i64> {
+    match limit.to_ascii_lowercase().as_str() {
+        "global" => Some(-2),
+        "unlimited" | "none" => Some(-1),
+        _ => None,
+    }
+}
+
+pub fn parse_ratio_limit(limit: &str) -> Result<f64, String> {
+    match parse_limit_keyword(limit) {
+        Some(keyword) => Ok(keyword as f64),
+        None => limit
+            .parse::<f64>()
+            .ok()
+            .filter(|ratio| *ratio >= 0.0)
+            .ok_or(format!("Invalid ratio: {}", limit)),
+    }
+}
+
+pub fn parse_time_limit(limit: &str) -> Result<i64, String> {
+    match parse_limit_keyword(limit) {
+        Some(keyword) => Ok(keyword),
+        None => limit
+            .parse::<i64>()
+            .ok()
+            .filter(|minutes| *minutes >= 0)
+            .ok_or(format!("Invalid number of minutes: {}", limit)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_rate, parse_ratio_limit, parse_time_limit};
+
+    #[test]
+    fn rates_with_units() {
+        assert_eq!(parse_rate("1024"), Ok(1024));
+        assert_eq!(parse_rate("500b"), Ok(500));
+        assert_eq!(parse_rate("500 KB/s"), Ok(500_000));
+        assert_eq!(parse_rate("1.5k"), Ok(1500));
+        assert_eq!(parse_rate("5MiB/s"), Ok(5 * 1024 * 1024));
+        assert_eq!(parse_rate("2 mb"), Ok(2_000_000));
+        assert_eq!(parse_rate("1GiB"), Ok(1024 * 1024 * 1024));
+    }
+
+    #[test]
+    fn unlimited_rate() {
+        assert_eq!(parse_rate("unlimited"), Ok(0));
+        assert_eq!(parse_rate("Unlimited"), Ok(0));
+        assert_eq!(parse_rate("0"), Ok(0));
+    }
+
+    #[test]
+    fn invalid_rates() {
+        assert!(parse_rate("").is_err());
+        assert!(parse_rate("-5").is_err());
+        assert!(parse_rate("fast").is_err());
+        assert!(parse_rate("5 TB").is_err());
+        assert!(parse_rate("5MiB/h").is_err());
+    }
+
+    #[test]
+    fn ratio_limits() {
+        assert_eq!(parse_ratio_limit("1.5"), Ok(1.5));
+        assert_eq!(parse_ratio_limit("0"), Ok(0.0));
+        assert_eq!(parse_ratio_limit("global"), Ok(-2.0));
+        assert_eq!(parse_ratio_limit("unlimited"), Ok(-1.0));
+        assert_eq!(parse_ratio_limit("None"), Ok(-1.0));
+        assert!(parse_ratio_limit("-1").is_err());
+        assert!(parse_ratio_limit("high").is_err());
+    }
+
+    #[test]
+    fn time_limits() {
+        assert_eq!(parse_time_limit("90"), Ok(90));
+        assert_eq!(parse_time_limit("GLOBAL"), Ok(-2));
+        assert_eq!(parse_time_limit("unlimited"), Ok(-1));
+        assert!(parse_time_limit("-2").is_err());
+        assert!(parse_time_limit("1.5").is_err());
+        assert!(parse_time_limit("1h").is_err());
+    }
 }
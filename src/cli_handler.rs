@@ -1,11 +1,16 @@
-use std::process::exit;
+use std::{process::exit, thread, time::Duration};
 
-use crate::cli::BaseCommand;
+use crate::cli::{BaseCommand, OutputFormat, TorrentColumn, TorrentListArgs, TorrentSelector};
 use crate::config::RequestInfo;
+use crate::render::{render, render_file_pieces, render_torrent_table};
 use crate::{backend::*, cli, Config};
 use directories::ProjectDirs;
+use regex::Regex;
+use url::Url;
 
 pub fn handle_cli(args: BaseCommand, dirs: &ProjectDirs, config: &mut Config) {
+    let output = args.output;
+
     match args.commands {
         /*
         AUTH SUBCOMMAND
@@ -23,7 +28,7 @@ pub fn handle_cli(args: BaseCommand, dirs: &ProjectDirs, config: &mut Config) {
                 }
             }
             cli::AuthCommands::List { show_secrets } => {
-                config.list_cookies(show_secrets);
+                render(&config.list_cookies(show_secrets), output);
             }
             cli::AuthCommands::Add {
                 url,
@@ -48,47 +53,342 @@ pub fn handle_cli(args: BaseCommand, dirs: &ProjectDirs, config: &mut Config) {
         /*
         TORRENT SUBCOMMAND
          */
+        // Migrating doesn't need a default url if both instances are given
+        cli::Commands::Torrent(cli::Torrent {
+            commands:
+                cli::TorrentCommands::Migrate {
+                    selector,
+                    from,
+                    to,
+                    delete,
+                },
+        }) => {
+            let source = match &from {
+                Some(url) => get_info_for_url(config, url),
+                None => get_info_if_default(config),
+            };
+            let destination = get_info_for_url(config, &to);
+
+            if source.url == destination.url {
+                eprintln!("The source and destination have to be different instances.");
+                exit(1);
+            }
+
+            let torrents = resolve_torrents(&source, &selector);
+            migrate_torrents(&source, &destination, &torrents, delete);
+        }
         cli::Commands::Torrent(args) => {
             let info = get_info_if_default(&config);
 
             match args.commands {
-                cli::TorrentCommands::List {
-                    sort,
-                    reverse,
-                    limit,
-                    interval,
-                } => {
-                    list_torrents(
-                        &info,
-                        sort.unwrap_or(cli::TorrentSortingOptions::Name),
-                        reverse,
-                        limit,
-                        interval,
-                    );
+                cli::TorrentCommands::List(args) => print_torrent_list(&info, &args, output),
+                cli::TorrentCommands::Migrate { .. } => unreachable!(),
+                cli::TorrentCommands::Add { inputs, options } => {
+                    add_torrents(&info, inputs, &options)
                 }
-                cli::TorrentCommands::Add { url_or_path, pause } => {
-                    add_torrent(&info, url_or_path, pause)
+                cli::TorrentCommands::Export { selector, out } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    export_torrents(&info, &torrents, &out);
                 }
                 cli::TorrentCommands::Delete {
-                    hashes,
+                    selector,
                     delete_files,
                 } => {
-                    if hashes.is_empty() {
-                        eprintln!("At least one hash must be provided!");
+                    let hashes = selected_hashes(&info, &selector);
+                    delete_torrents(&info, hashes, delete_files)
+                }
+                cli::TorrentCommands::Pause { selector } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    pause_torrents(&info, &torrents, selector.is_all());
+                }
+                cli::TorrentCommands::Resume { selector } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    resume_torrents(&info, &torrents, selector.is_all());
+                }
+                cli::TorrentCommands::Content { selector } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    match torrent_content(&info, torrent.hash) {
+                        Some(files) => render(&files, output),
+                        None => eprintln!("Request failed, make sure the hash is valid"),
+                    }
+                }
+                cli::TorrentCommands::Pieces { selector, files } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    // The hashes are only part of the machine readable output
+                    let with_hashes = output != OutputFormat::Human;
+                    let Some(pieces) = torrent_pieces(&info, &torrent.hash, with_hashes) else {
+                        eprintln!("Request failed, make sure the hash is valid");
                         exit(1);
+                    };
+
+                    render(&pieces, output);
+
+                    if files && output == OutputFormat::Human {
+                        match torrent_content(&info, torrent.hash) {
+                            Some(files) => render_file_pieces(&pieces, &files),
+                            None => eprintln!("Fetching the files of {} failed", torrent.name),
+                        }
                     }
+                }
+                cli::TorrentCommands::Info { selector } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    match torrent_details(&info, torrent) {
+                        Some(details) => render(&[details], output),
+                        None => eprintln!("Request failed, make sure the hash is valid"),
+                    }
+                }
+                cli::TorrentCommands::Trackers { selector } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    match list_trackers(&info, &torrent.hash) {
+                        Some(trackers) => render(&trackers, output),
+                        None => eprintln!("Fetching the trackers of {} failed", torrent.name),
+                    }
+                }
+                cli::TorrentCommands::AddTracker { selector, urls } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    add_trackers(&info, &torrents, urls);
+                }
+                cli::TorrentCommands::EditTracker { selector, old, new } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    edit_tracker(&info, &torrents, old, new);
+                }
+                cli::TorrentCommands::RemoveTracker { selector, urls } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    remove_trackers(&info, &torrents, urls);
+                }
+                cli::TorrentCommands::Webseeds { selector } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    match list_webseeds(&info, &torrent.hash) {
+                        Some(webseeds) => render(&webseeds, output),
+                        None => eprintln!("Request failed, make sure the hash is valid"),
+                    }
+                }
+                cli::TorrentCommands::AddWebseed { selector, urls } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    add_webseeds(&info, &torrents, urls);
+                }
+                cli::TorrentCommands::EditWebseed { selector, old, new } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    edit_webseed(&info, &torrents, old, new);
+                }
+                cli::TorrentCommands::RemoveWebseed { selector, urls } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    remove_webseeds(&info, &torrents, urls);
+                }
+                cli::TorrentCommands::Peers { selector } => {
+                    let torrent = resolve_torrent(&info, &selector);
+                    match list_peers(&info, &torrent.hash) {
+                        Some(peers) => render(&peers, output),
+                        None => eprintln!("Request failed, make sure the hash is valid"),
+                    }
+                }
+                cli::TorrentCommands::AddPeers { selector, peers } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    add_peers(&info, &torrents, selector.is_all(), peers);
+                }
+                cli::TorrentCommands::Files(args) => match args.commands {
+                    cli::FilesCommands::SetPriority {
+                        hash,
+                        files,
+                        priority,
+                    } => {
+                        let torrent = resolve_hash(&info, &hash);
+                        set_file_priority(&info, &torrent, &files, priority);
+                    }
+                },
+                cli::TorrentCommands::Rename { hash, name } => {
+                    let torrent = resolve_hash(&info, &hash);
+                    rename_torrent(&info, &torrent, name);
+                }
+                cli::TorrentCommands::RenameFile {
+                    hash,
+                    old_path,
+                    new_path,
+                    regex,
+                    dry_run,
+                } => {
+                    let torrent = resolve_hash(&info, &hash);
 
-                    delete_torrents(&info, hashes, delete_files)
+                    if regex {
+                        let pattern = match Regex::new(&old_path) {
+                            Ok(pattern) => pattern,
+                            Err(e) => {
+                                eprintln!("Invalid regex: {}", e);
+                                exit(1);
+                            }
+                        };
+                        rename_files_regex(&info, &torrent, &pattern, &new_path, dry_run);
+                    } else if rename_file(&info, &torrent.hash, &old_path, &new_path) {
+                        println!("Renamed {} to {}", old_path, new_path);
+                    } else {
+                        eprintln!("Renaming {} failed, make sure the path exists", old_path);
+                    }
                 }
-                cli::TorrentCommands::Pause { hash } => pause_torrent(&info, hash),
-                cli::TorrentCommands::Resume { hash } => resume_torrent(&info, hash),
-                cli::TorrentCommands::Content { hash } => {
-                    if torrent_content(&info, hash).is_none() {
-                        eprintln!("Request failed, make sure the hash is valid");
+                cli::TorrentCommands::RenameFolder {
+                    hash,
+                    old_path,
+                    new_path,
+                } => {
+                    let torrent = resolve_hash(&info, &hash);
+
+                    if rename_folder(&info, &torrent.hash, &old_path, &new_path) {
+                        println!("Renamed {} to {}", old_path, new_path);
+                    } else {
+                        eprintln!("Renaming {} failed, make sure the path exists", old_path);
+                    }
+                }
+                cli::TorrentCommands::Move {
+                    location,
+                    selector,
+                    wait,
+                } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    if set_location(&info, &torrents, selector.is_all(), &location) && wait {
+                        wait_until_moved(&info, &torrents, &location);
+                    }
+                }
+                cli::TorrentCommands::SetSavePath {
+                    path,
+                    selector,
+                    wait,
+                } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    if set_save_path(&info, &torrents, selector.is_all(), &path) && wait {
+                        wait_until_moved(&info, &torrents, &path);
                     }
                 }
-                cli::TorrentCommands::Recheck { hash } => recheck(&info, hash),
-                cli::TorrentCommands::Reannounce { hash } => reannounce(&info, hash),
+                cli::TorrentCommands::SetDownloadPath {
+                    path,
+                    selector,
+                    wait,
+                } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    if set_download_path(&info, &torrents, selector.is_all(), &path) && wait {
+                        wait_until_moved(&info, &torrents, &path);
+                    }
+                }
+                cli::TorrentCommands::Limit { selector, dl, ul } => {
+                    let torrents = resolve_torrents(&info, &selector);
+
+                    if dl.is_none() && ul.is_none() {
+                        render(&get_limits(&info, torrents), output);
+                    } else {
+                        set_speed_limits(&info, &torrents, selector.is_all(), dl, ul);
+                    }
+                }
+                cli::TorrentCommands::ShareLimit {
+                    selector,
+                    ratio,
+                    seeding_time,
+                    inactive_seeding_time,
+                } => {
+                    let torrents = resolve_torrents(&info, &selector);
+
+                    if ratio.is_none() && seeding_time.is_none() && inactive_seeding_time.is_none()
+                    {
+                        render(&get_limits(&info, torrents), output);
+                    } else {
+                        set_share_limits(
+                            &info,
+                            &torrents,
+                            ratio,
+                            seeding_time,
+                            inactive_seeding_time,
+                        );
+                    }
+                }
+                cli::TorrentCommands::Queue {
+                    direction,
+                    selector,
+                } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    move_in_queue(&info, &torrents, selector.is_all(), direction);
+                }
+                cli::TorrentCommands::Set { selector, flags } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    set_torrent_flags(&info, &torrents, selector.is_all(), &flags);
+                }
+                cli::TorrentCommands::Recheck { selector } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    recheck(&info, &torrents, selector.is_all());
+                }
+                cli::TorrentCommands::Reannounce { selector } => {
+                    let torrents = resolve_torrents(&info, &selector);
+                    reannounce(&info, &torrents, selector.is_all());
+                }
+                cli::TorrentCommands::SetCategory {
+                    selector,
+                    new_category,
+                } => {
+                    let hashes = selected_hashes(&info, &selector);
+                    set_category(&info, hashes, new_category)
+                }
+                cli::TorrentCommands::AddTags { selector, tags } => {
+                    let hashes = selected_hashes(&info, &selector);
+                    add_tags(&info, hashes, tags)
+                }
+                cli::TorrentCommands::RemoveTags { selector, tags } => {
+                    let hashes = selected_hashes(&info, &selector);
+                    remove_tags(&info, hashes, tags)
+                }
+            }
+        }
+
+        /*
+        CATEGORY SUBCOMMAND
+         */
+        cli::Commands::Category(args) => {
+            let info = get_info_if_default(&config);
+
+            match args.commands {
+                cli::CategoryCommands::List => render(&list_categories(&info), output),
+                cli::CategoryCommands::Create { name, save_path } => {
+                    create_category(&info, name, save_path)
+                }
+                cli::CategoryCommands::Edit { name, save_path } => {
+                    edit_category(&info, name, save_path)
+                }
+                cli::CategoryCommands::Remove { names } => remove_categories(&info, names),
+            }
+        }
+
+        /*
+        TAG SUBCOMMAND
+         */
+        cli::Commands::Tag(args) => {
+            let info = get_info_if_default(&config);
+
+            match args.commands {
+                cli::TagCommands::List => render(&list_tags(&info), output),
+                cli::TagCommands::Create { tags } => create_tags(&info, tags),
+                cli::TagCommands::Delete { tags } => delete_tags(&info, tags),
+            }
+        }
+
+        /*
+        TRACKER SUBCOMMAND
+         */
+        cli::Commands::Tracker(args) => {
+            let info = get_info_if_default(&config);
+
+            match args.commands {
+                cli::TrackerCommands::Replace {
+                    pattern,
+                    replacement,
+                    dry_run,
+                } => replace_trackers(&info, &pattern, &replacement, dry_run),
+            }
+        }
+
+        /*
+        BACKUP SUBCOMMAND
+         */
+        cli::Commands::Backup(args) => {
+            let info = get_info_if_default(&config);
+
+            match args.commands {
+                cli::BackupCommands::Create { file } => create_backup(&info, &file),
+                cli::BackupCommands::Restore { file } => restore_backup(&info, &file),
             }
         }
 
@@ -109,7 +409,8 @@ pub fn handle_cli(args: BaseCommand, dirs: &ProjectDirs, config: &mut Config) {
             match args.commands {
                 cli::GlobalCommands::Shutdown => shutdown(&info),
                 cli::GlobalCommands::Version => version(&info),
-                cli::GlobalCommands::Log => logs(&info),
+                cli::GlobalCommands::Log => render(&logs(&info), output),
+                cli::GlobalCommands::BanPeers { peers } => ban_peers(&info, peers),
                 cli::GlobalCommands::AltSpeed { toggle } => {
                     if toggle {
                         toggle_alt_speed(&info);
@@ -125,7 +426,7 @@ pub fn handle_cli(args: BaseCommand, dirs: &ProjectDirs, config: &mut Config) {
     }
 }
 
-fn get_info_if_default(config: &Config) -> RequestInfo {
+fn get_info_if_default(config: &Config) -> RequestInfo<'_> {
     if config.default.is_none() || config.cookies.is_empty() {
         eprintln!("No (default) url configured. Please configure a url using the auth subcommand!");
         exit(1);
@@ -133,3 +434,73 @@ fn get_info_if_default(config: &Config) -> RequestInfo {
 
     config.get_request_info()
 }
+
+fn get_info_for_url<'a>(config: &'a Config, url: &Url) -> RequestInfo<'a> {
+    match config.get_request_info_for(url) {
+        Some(info) => info,
+        None => {
+            eprintln!(
+                "Url {} is not registered. Use the auth subcommand to add it.",
+                url
+            );
+            exit(1);
+        }
+    }
+}
+
+fn selected_hashes(info: &RequestInfo, selector: &TorrentSelector) -> Vec<String> {
+    resolve_torrents(info, selector)
+        .into_iter()
+        .map(|t| t.hash)
+        .collect()
+}
+
+fn print_torrent_list(info: &RequestInfo, args: &TorrentListArgs, output: OutputFormat) {
+    let sort_by = args.sort.unwrap_or(cli::TorrentSortingOptions::Name);
+    let sort_string = format!("{:?}", sort_by).to_ascii_lowercase();
+
+    let columns = match (&args.columns, args.table) {
+        (Some(columns), _) => Some(columns.as_slice()),
+        (None, true) => Some(TorrentColumn::DEFAULT),
+        (None, false) => None,
+    };
+
+    let mut refresh_counter = 0;
+    loop {
+        let torrents = list_torrents(info, sort_by, args.reverse, args.limit, &args.filter);
+
+        match (columns, output) {
+            (Some(columns), OutputFormat::Human) => render_torrent_table(&torrents, columns),
+            _ => render(&torrents, output),
+        }
+
+        // Only the human readable output gets a summary, to keep the other formats parseable
+        if output == OutputFormat::Human {
+            println!(
+                "Found {} torrents, sorted by: {} {}",
+                torrents.len(),
+                sort_string,
+                match args.reverse {
+                    true => "(reversed)",
+                    false => "",
+                }
+            );
+
+            if let Some(interval) = args.interval {
+                println!("Refreshed {} times, every {}ms", refresh_counter, interval);
+            }
+        }
+
+        let Some(interval) = args.interval else {
+            break;
+        };
+
+        thread::sleep(Duration::from_millis(interval));
+        refresh_counter += 1;
+
+        if output == OutputFormat::Human {
+            // Clear screen control char
+            print!("{}[2J", 27 as char);
+        }
+    }
+}
@@ -22,39 +22,47 @@ pub struct Config {
     pub default: Option<Url>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct CookieEntry {
+    pub url: Url,
+    pub default: bool,
+    /// None if the secrets are redacted
+    pub cookie: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct RequestInfo<'a> {
+    #[allow(unused)]
     pub jar: Arc<Jar>,
     pub client: Client,
     pub url: &'a Url,
 }
 
 impl Config {
-    pub fn get_request_info(&self) -> RequestInfo {
+    pub fn get_request_info(&self) -> RequestInfo<'_> {
         if let Some(url) = &self.default {
-            let cookie = self
-                .cookies
-                .get(url)
-                .expect("Invalid default value")
-                .to_owned();
-
-            let jar = Arc::new(Jar::default());
-            jar.add_cookie_str(&cookie, &url);
-            let client = ClientBuilder::new()
-                .cookie_provider(jar.clone())
-                .build()
-                .unwrap();
-
-            return RequestInfo {
-                jar,
-                client,
-                url: &url,
-            };
+            return self
+                .get_request_info_for(url)
+                .expect("Invalid default value");
         } else {
             panic!("No Default value!");
         }
     }
 
+    /// Like `get_request_info`, but for any stored url. None if the url is not stored
+    pub fn get_request_info_for(&self, url: &Url) -> Option<RequestInfo<'_>> {
+        let (url, cookie) = self.cookies.get_key_value(url)?;
+
+        let jar = Arc::new(Jar::default());
+        jar.add_cookie_str(cookie, url);
+        let client = ClientBuilder::new()
+            .cookie_provider(jar.clone())
+            .build()
+            .unwrap();
+
+        Some(RequestInfo { jar, client, url })
+    }
+
     pub fn remove_url(&mut self, url: &Url) {
         if self.default == Some(url.clone()) {
             self.default = None;
@@ -68,38 +76,26 @@ impl Config {
     }
 
     pub fn activate_url(&mut self, url: &Url) {
-        if self.cookies.get(&url).is_none() {
+        if !self.cookies.contains_key(&url) {
             println!("{} is not stored", &url);
             return;
         }
         self.default = Some(url.clone());
     }
 
-    pub fn list_cookies(&self, show_secrets: bool) {
-        if self.cookies.is_empty() {
-            println!("No stored cookies!");
-            return;
-        }
-
-        if !show_secrets {
-            println!("NOTE: secrets are redacted. To reveal, pass --show-secrets\n")
-        }
-
-        println!("DEFAULT\tURL\tCOOKIE");
-        for (url, cookie) in &self.cookies {
-            if self.default == Some(url.clone()) {
-                print!("[*]\t")
-            } else {
-                print!("[ ]\t")
-            }
-            print!("{}: ", url);
-            if show_secrets {
-                print!("{}", cookie);
-            } else {
-                print!("[REDACTED]")
-            }
-            println!()
-        }
+    pub fn list_cookies(&self, show_secrets: bool) -> Vec<CookieEntry> {
+        let mut entries: Vec<CookieEntry> = self
+            .cookies
+            .iter()
+            .map(|(url, cookie)| CookieEntry {
+                url: url.clone(),
+                default: self.default.as_ref() == Some(url),
+                cookie: show_secrets.then(|| cookie.clone()),
+            })
+            .collect();
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+
+        entries
     }
 
     pub fn save_config(&self, dirs: &ProjectDirs) {
@@ -114,10 +110,10 @@ impl Config {
     pub fn from_file(dirs: &ProjectDirs) -> Self {
         let dir = dirs.config_dir();
         if !dir.exists() {
-            create_dir_all(&dir).expect("Failed creating config dir");
+            create_dir_all(dir).expect("Failed creating config dir");
         }
 
-        let file = match read_to_string(&dir.join(CONFIG_FILE)) {
+        let file = match read_to_string(dir.join(CONFIG_FILE)) {
             Ok(f) => f,
             Err(_) => return Config::default(),
         };
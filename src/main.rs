@@ -10,6 +10,7 @@ mod backend;
 mod cli;
 mod cli_handler;
 mod config;
+mod render;
 
 fn main() {
     let args = BaseCommand::parse();
@@ -0,0 +1,42 @@
+use crate::config::CookieEntry;
+
+use super::Render;
+
+impl Render for CookieEntry {
+    const HEADERS: &'static [&'static str] = &["default", "url", "cookie"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.default.to_string(),
+            self.url.to_string(),
+            self.cookie.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        if items.is_empty() {
+            println!("No stored cookies!");
+            return;
+        }
+
+        if items.iter().any(|entry| entry.cookie.is_none()) {
+            println!("NOTE: secrets are redacted. To reveal, pass --show-secrets\n")
+        }
+
+        println!("DEFAULT\tURL\tCOOKIE");
+        for entry in items {
+            if entry.default {
+                print!("[*]\t")
+            } else {
+                print!("[ ]\t")
+            }
+            print!("{}: ", entry.url);
+            if let Some(cookie) = &entry.cookie {
+                print!("{}", cookie);
+            } else {
+                print!("[REDACTED]")
+            }
+            println!()
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::backend::CategoryResponse;
+
+use super::Render;
+
+impl Render for CategoryResponse {
+    const HEADERS: &'static [&'static str] = &["name", "save_path"];
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.save_path.clone()]
+    }
+
+    fn human(items: &[Self]) {
+        if items.is_empty() {
+            println!("No categories configured.");
+            return;
+        }
+
+        println!("NAME\tSAVE PATH\n");
+        for category in items {
+            let save_path = match category.save_path.is_empty() {
+                true => "[default]",
+                false => &category.save_path,
+            };
+
+            println!("{}\t{}", category.name, save_path);
+        }
+    }
+}
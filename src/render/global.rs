@@ -0,0 +1,26 @@
+use crate::backend::LogResponse;
+
+use super::{epoch_to_datetime, Render};
+
+impl Render for LogResponse {
+    const HEADERS: &'static [&'static str] = &["id", "type", "timestamp", "message"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.r#type.to_string(),
+            self.timestamp.to_string(),
+            self.message.clone(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        println!("ID\tTYPE\tTIME\t\t\tMESSAGE\n");
+
+        for log in items {
+            let time = epoch_to_datetime(log.timestamp);
+
+            println!("{}\t{}\t{}\t{}", log.id, log.level(), time, log.message);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use humansize::{format_size, BINARY};
+
+use crate::backend::TorrentLimits;
+
+use super::{format_duration, print_table, Align, Column, Render};
+
+impl Render for TorrentLimits {
+    const HEADERS: &'static [&'static str] = &[
+        "hash",
+        "name",
+        "dl_limit",
+        "up_limit",
+        "ratio_limit",
+        "seeding_time_limit",
+        "inactive_seeding_time_limit",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.hash.clone(),
+            self.name.clone(),
+            self.dl_limit.to_string(),
+            self.up_limit.to_string(),
+            self.ratio_limit.to_string(),
+            self.seeding_time_limit.to_string(),
+            self.inactive_seeding_time_limit.to_string(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        let column = |header, align, shrink| Column {
+            header,
+            align,
+            shrink,
+        };
+        let columns = [
+            column("NAME", Align::Left, true),
+            column("DOWN", Align::Right, false),
+            column("UP", Align::Right, false),
+            column("RATIO", Align::Right, false),
+            column("SEEDING TIME", Align::Right, false),
+            column("INACTIVE TIME", Align::Right, false),
+        ];
+
+        let rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|t| {
+                vec![
+                    t.name.clone(),
+                    speed_limit(t.dl_limit),
+                    speed_limit(t.up_limit),
+                    share_limit(t.ratio_limit, |ratio| format!("{:.2}", ratio)),
+                    share_limit(t.seeding_time_limit as f64, |minutes| {
+                        format_duration(minutes as u64 * 60)
+                    }),
+                    share_limit(t.inactive_seeding_time_limit as f64, |minutes| {
+                        format_duration(minutes as u64 * 60)
+                    }),
+                ]
+            })
+            .collect();
+
+        print_table(&columns, &rows);
+    }
+}
+
+fn speed_limit(limit: u64) -> String {
+    match limit {
+        0 => "unlimited".to_string(),
+        limit => format!("{}/s", format_size(limit, BINARY)),
+    }
+}
+
+// -2 means the global limit is used, -1 means there is no limit
+pub fn share_limit(limit: f64, format: impl Fn(f64) -> String) -> String {
+    if limit == -2.0 {
+        "global".to_string()
+    } else if limit < 0.0 {
+        "unlimited".to_string()
+    } else {
+        format(limit)
+    }
+}
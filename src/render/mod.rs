@@ -0,0 +1,256 @@
+use std::{
+    io::{self, ErrorKind, Write},
+    process::exit,
+};
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::Serialize;
+
+use crate::{
+    backend::{PIECE_DOWNLOADING, PIECE_HAVE},
+    cli::OutputFormat,
+};
+
+mod auth;
+mod categories;
+mod global;
+mod limits;
+mod peers;
+mod pieces;
+pub use pieces::render_file_pieces;
+mod tags;
+
+mod table;
+pub use table::*;
+
+mod torrents;
+pub use torrents::*;
+
+mod trackers;
+mod webseeds;
+
+/// Implemented by all data returned from the backend that can be printed by a listing command
+pub trait Render: Serialize + Sized {
+    /// The column names used for csv and tsv output
+    const HEADERS: &'static [&'static str];
+
+    /// The values of a single csv or tsv row, in the same order as `HEADERS`
+    fn row(&self) -> Vec<String>;
+
+    /// Prints the items in the human readable format
+    fn human(items: &[Self]);
+}
+
+pub fn render<T: Render>(items: &[T], format: OutputFormat) {
+    let mut out = io::stdout().lock();
+
+    let result = match format {
+        OutputFormat::Human => {
+            T::human(items);
+            Ok(())
+        }
+        OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(items).unwrap()),
+        OutputFormat::Csv => print_delimited(&mut out, items, ",", csv_escape),
+        OutputFormat::Tsv => print_delimited(&mut out, items, "\t", tsv_escape),
+    };
+
+    match result {
+        Ok(()) => {}
+        // The output was piped into a program that stopped reading, e.g. `head`
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => exit(0),
+        Err(e) => {
+            eprintln!("Failed writing the output: {}", e);
+            exit(1);
+        }
+    }
+}
+
+fn print_delimited<T: Render>(
+    out: &mut impl Write,
+    items: &[T],
+    delimiter: &str,
+    escape: fn(&str) -> String,
+) -> io::Result<()> {
+    let headers: Vec<String> = T::HEADERS.iter().map(|h| escape(h)).collect();
+    writeln!(out, "{}", headers.join(delimiter))?;
+
+    for item in items {
+        let row: Vec<String> = item.row().iter().map(|v| escape(v)).collect();
+        writeln!(out, "{}", row.join(delimiter))?;
+    }
+
+    Ok(())
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// TSV has no quoting, so characters that would break the layout are replaced
+fn tsv_escape(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+/// A value as it is written in the json output, so csv and tsv rows use the same values
+fn json_value<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value).unwrap() {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+pub fn progress_render(progress: f64) -> String {
+    let progress = (progress * 10.0) as u32;
+    let mut s = "<".to_string();
+
+    for _ in 0..progress {
+        s.push('#');
+    }
+
+    for _ in progress..10 {
+        s.push('_');
+    }
+    s.push('>');
+
+    return s;
+}
+
+/// Draws the pieces as a bar of the given width, where every character stands for one or more pieces:
+/// `#` if all of them are downloaded, `=` if any is being downloaded, `-` if some are downloaded and
+/// `_` if none are
+pub fn pieces_render(states: &[u8], width: usize) -> String {
+    let mut s = "<".to_string();
+
+    if !states.is_empty() {
+        for i in 0..width {
+            let start = i * states.len() / width;
+            let end = ((i + 1) * states.len() / width).max(start + 1);
+            let bucket = &states[start..end];
+
+            s.push(if bucket.iter().all(|&p| p == PIECE_HAVE) {
+                '#'
+            } else if bucket.contains(&PIECE_DOWNLOADING) {
+                '='
+            } else if bucket.contains(&PIECE_HAVE) {
+                '-'
+            } else {
+                '_'
+            });
+        }
+    }
+    s.push('>');
+
+    s
+}
+
+pub fn epoch_to_datetime(epoch: i64) -> NaiveDateTime {
+    DateTime::from_timestamp(epoch, 0).unwrap().naive_utc()
+}
+
+/// Formats a duration as its two most significant units, e.g. `2h 15m`
+pub fn format_duration(seconds: u64) -> String {
+    let units = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+
+    let parts: Vec<String> = units
+        .iter()
+        .scan(seconds, |rest, (unit, suffix)| {
+            let value = *rest / unit;
+            *rest %= unit;
+            Some((value, suffix))
+        })
+        .skip_while(|(value, _)| *value == 0)
+        .take(2)
+        .map(|(value, suffix)| format!("{}{}", value, suffix))
+        .collect();
+
+    match parts.is_empty() {
+        true => "0s".to_string(),
+        false => parts.join(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::{TorrentFileResponse, TorrentInfoResponse};
+
+    use super::{csv_escape, print_delimited, tsv_escape, Render};
+
+    fn file() -> TorrentFileResponse {
+        serde_json::from_str(
+            r#"{"index":0,"name":"a, \"b\".iso","piece_range":[0,10],"progress":0.5,"size":5000,"priority":6,"availability":0.8}"#,
+        )
+        .unwrap()
+    }
+
+    fn delimited(delimiter: &str, escape: fn(&str) -> String) -> String {
+        let mut out = Vec::new();
+        print_delimited(&mut out, &[file()], delimiter, escape).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn csv_quotes_special_characters() {
+        assert_eq!(csv_escape("plain"), "plain");
+        assert_eq!(csv_escape("a,b"), "\"a,b\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn tsv_replaces_special_characters() {
+        assert_eq!(tsv_escape("plain"), "plain");
+        assert_eq!(tsv_escape("a\tb\nc\rd"), "a b c d");
+        assert_eq!(tsv_escape("a,\"b\""), "a,\"b\"");
+    }
+
+    #[test]
+    fn csv_row() {
+        assert_eq!(
+            delimited(",", csv_escape),
+            "index,name,progress,size,first_piece,last_piece,priority,availability\n\
+             0,\"a, \"\"b\"\".iso\",0.5,5000,0,10,6,0.8\n"
+        );
+    }
+
+    #[test]
+    fn tsv_row() {
+        assert_eq!(
+            delimited("\t", tsv_escape),
+            "index\tname\tprogress\tsize\tfirst_piece\tlast_piece\tpriority\tavailability\n\
+             0\ta, \"b\".iso\t0.5\t5000\t0\t10\t6\t0.8\n"
+        );
+    }
+
+    #[test]
+    fn json_row() {
+        let json = serde_json::to_value([file()]).unwrap();
+        assert_eq!(json[0]["priority"], 6);
+        assert_eq!(json[0]["name"], "a, \"b\".iso");
+    }
+
+    #[test]
+    fn torrent_rows_use_json_values() {
+        let torrent: TorrentInfoResponse = serde_json::from_str(
+            r#"{"hash":"aaaa","name":"a","progress":0.5,"ratio":0.1,"size":100,"state":"pausedDL","added_on":1690000000,"tags":"","category":"","dlspeed":0,"upspeed":0,"eta":8640000,"num_seeds":0,"num_leechs":0,"ratio_limit":-2,"seeding_time_limit":-2,"save_path":"/data","seq_dl":false,"f_l_piece_prio":false,"super_seeding":false,"force_start":false,"auto_tmm":false,"priority":1,"magnet_uri":""}"#,
+        )
+        .unwrap();
+        let json = serde_json::to_value(&torrent).unwrap();
+        let row = torrent.row();
+        let column = |name| {
+            let index = TorrentInfoResponse::HEADERS
+                .iter()
+                .position(|&h| h == name)
+                .unwrap();
+            row[index].clone()
+        };
+
+        assert_eq!(column("state"), "pausedDL");
+        assert_eq!(json["state"], "pausedDL");
+        assert_eq!(column("priority"), "1");
+        assert_eq!(json["priority"], 1);
+    }
+}
@@ -0,0 +1,102 @@
+use humansize::{format_size, DECIMAL};
+
+use crate::backend::PeerResponse;
+
+use super::{print_table, Align, Column, Render};
+
+impl Render for PeerResponse {
+    const HEADERS: &'static [&'static str] = &[
+        "ip",
+        "port",
+        "client",
+        "flags",
+        "connection",
+        "country",
+        "country_code",
+        "progress",
+        "dl_speed",
+        "up_speed",
+        "downloaded",
+        "uploaded",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.ip.clone(),
+            self.port.to_string(),
+            self.client.clone(),
+            self.flags.clone(),
+            self.connection.clone(),
+            self.country.clone(),
+            self.country_code.clone(),
+            self.progress.to_string(),
+            self.dl_speed.to_string(),
+            self.up_speed.to_string(),
+            self.downloaded.to_string(),
+            self.uploaded.to_string(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        if items.is_empty() {
+            println!("No connected peers.");
+            return;
+        }
+
+        let columns = [
+            Column {
+                header: "ADDRESS",
+                align: Align::Left,
+                shrink: false,
+            },
+            Column {
+                header: "CLIENT",
+                align: Align::Left,
+                shrink: true,
+            },
+            Column {
+                header: "FLAGS",
+                align: Align::Left,
+                shrink: false,
+            },
+            Column {
+                header: "COUNTRY",
+                align: Align::Left,
+                shrink: false,
+            },
+            Column {
+                header: "PROGRESS",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "DOWN",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "UP",
+                align: Align::Right,
+                shrink: false,
+            },
+        ];
+
+        let rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|p| {
+                vec![
+                    format!("{}:{}", p.ip, p.port),
+                    p.client.clone(),
+                    p.flags.clone(),
+                    p.country.clone(),
+                    format!("{:.1}%", p.progress * 100.0),
+                    format!("{}/s", format_size(p.dl_speed, DECIMAL)),
+                    format!("{}/s", format_size(p.up_speed, DECIMAL)),
+                ]
+            })
+            .collect();
+
+        print_table(&columns, &rows);
+        println!("\n{} connected peer(s).", items.len());
+    }
+}
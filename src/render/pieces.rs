@@ -0,0 +1,65 @@
+use terminal_size::{terminal_size, Width};
+
+use crate::backend::{PieceInfo, TorrentFileResponse, PIECE_DOWNLOADING, PIECE_HAVE};
+
+use super::{pieces_render, Render};
+
+// Used when not writing to a terminal
+const DEFAULT_WIDTH: usize = 80;
+
+impl Render for PieceInfo {
+    const HEADERS: &'static [&'static str] = &["index", "state", "hash"];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.index.to_string(),
+            self.state.to_string(),
+            self.hash.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        let states: Vec<u8> = items.iter().map(|p| p.state).collect();
+        let count = |state| states.iter().filter(|&&s| s == state).count();
+        let have = count(PIECE_HAVE);
+
+        println!("{}", pieces_render(&states, bar_width(0)));
+        println!(
+            "Have {} of {} pieces ({:.1}%), {} downloading",
+            have,
+            states.len(),
+            match states.len() {
+                0 => 0.0,
+                len => have as f64 / len as f64 * 100.0,
+            },
+            count(PIECE_DOWNLOADING)
+        );
+        println!("Legend: # have, - partially have, = downloading, _ missing");
+    }
+}
+
+/// Draws a bar for every file, showing the pieces in its `piece_range`
+pub fn render_file_pieces(pieces: &[PieceInfo], files: &[TorrentFileResponse]) {
+    for file in files {
+        let [first, last] = file.piece_range;
+        let states: Vec<u8> = pieces
+            .iter()
+            .skip(first as usize)
+            .take(last.saturating_sub(first) as usize + 1)
+            .map(|p| p.state)
+            .collect();
+
+        println!("\n{} ({:.1}%)", file.name, file.progress * 100.0);
+        println!("  {}", pieces_render(&states, bar_width(2)));
+    }
+}
+
+// The width of a bar (without the brackets) that fits the terminal after the indent
+fn bar_width(indent: usize) -> usize {
+    let width = match terminal_size() {
+        Some((Width(width), _)) => width as usize,
+        None => DEFAULT_WIDTH,
+    };
+
+    width.saturating_sub(indent + 2).max(1)
+}
@@ -0,0 +1,71 @@
+use terminal_size::{terminal_size, Width};
+
+const SEPARATOR: &str = "  ";
+// A shrinkable column is never truncated to less than this
+const MIN_SHRINK_WIDTH: usize = 10;
+
+pub enum Align {
+    Left,
+    Right,
+}
+
+pub struct Column {
+    pub header: &'static str,
+    pub align: Align,
+    /// Whether this column may be truncated to make the table fit the terminal
+    pub shrink: bool,
+}
+
+pub fn print_table(columns: &[Column], rows: &[Vec<String>]) {
+    let mut widths: Vec<usize> = columns.iter().map(|c| c.header.chars().count()).collect();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    // When not writing to a terminal (e.g. piping into a file) nothing is truncated
+    if let Some((Width(term_width), _)) = terminal_size() {
+        let total = widths.iter().sum::<usize>() + SEPARATOR.len() * (widths.len() - 1);
+        let excess = total.saturating_sub(term_width as usize);
+
+        if let Some(i) = columns.iter().position(|c| c.shrink) {
+            let min = MIN_SHRINK_WIDTH.min(widths[i]);
+            widths[i] = widths[i].saturating_sub(excess).max(min);
+        }
+    }
+
+    let headers: Vec<String> = columns.iter().map(|c| c.header.to_string()).collect();
+    print_row(columns, &widths, &headers);
+    for row in rows {
+        print_row(columns, &widths, row);
+    }
+}
+
+fn print_row(columns: &[Column], widths: &[usize], row: &[String]) {
+    let cells: Vec<String> = columns
+        .iter()
+        .zip(widths)
+        .zip(row)
+        .map(|((column, &width), value)| {
+            let value = truncate(value, width);
+            match column.align {
+                Align::Left => format!("{:<width$}", value),
+                Align::Right => format!("{:>width$}", value),
+            }
+        })
+        .collect();
+
+    println!("{}", cells.join(SEPARATOR).trim_end());
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        return value.to_string();
+    }
+
+    let mut truncated: String = value.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+
+    truncated
+}
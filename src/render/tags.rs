@@ -0,0 +1,22 @@
+use crate::backend::TagResponse;
+
+use super::Render;
+
+impl Render for TagResponse {
+    const HEADERS: &'static [&'static str] = &["name"];
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone()]
+    }
+
+    fn human(items: &[Self]) {
+        if items.is_empty() {
+            println!("No tags configured.");
+            return;
+        }
+
+        for tag in items {
+            println!("{}", tag.name);
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use humansize::{format_size, DECIMAL};
+
+use crate::{
+    backend::{TorrentDetails, TorrentFileResponse, TorrentInfoResponse},
+    cli::TorrentColumn,
+};
+
+use super::{
+    epoch_to_datetime, format_duration, json_value, limits::share_limit, print_table,
+    progress_render, Align, Column, Render,
+};
+
+// qBittorrent reports this ETA for torrents that will never finish
+const INFINITE_ETA: u64 = 8640000;
+
+impl Render for TorrentInfoResponse {
+    const HEADERS: &'static [&'static str] = &[
+        "hash",
+        "name",
+        "progress",
+        "size",
+        "added_on",
+        "ratio",
+        "state",
+        "tags",
+        "category",
+        "dlspeed",
+        "upspeed",
+        "eta",
+        "num_seeds",
+        "num_leechs",
+        "priority",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.hash.clone(),
+            self.name.clone(),
+            self.progress.to_string(),
+            self.size.to_string(),
+            self.added_on.to_string(),
+            self.ratio.to_string(),
+            json_value(&self.state),
+            self.tags.clone(),
+            self.category.clone(),
+            self.dlspeed.to_string(),
+            self.upspeed.to_string(),
+            self.eta.to_string(),
+            self.num_seeds.to_string(),
+            self.num_leechs.to_string(),
+            self.priority.to_string(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        println!("\n");
+        for t in items {
+            let added_on = epoch_to_datetime(t.added_on);
+
+            println!("   | {}\n   |", t.name);
+            println!("   |  > Hash: {}", t.hash);
+            println!(
+                "   |  > Progress: {:.2}% {}",
+                t.progress * 100.0,
+                progress_render(t.progress)
+            );
+            println!("   |  > Size: {}", format_size(t.size, DECIMAL));
+            println!("   |  > Aded on: {}", added_on);
+            println!("   |  > Ratio: {:.2}", t.ratio);
+            println!("   |  > State: {} ({:#})", t.state, t.state);
+            if !t.tags.is_empty() {
+                println!("   |  > Tags: {}", t.tags);
+            }
+            if t.priority > 0 {
+                println!("   |  > Queue position: {}", t.priority);
+            }
+
+            println!("\n")
+        }
+    }
+}
+
+impl Render for TorrentDetails {
+    const HEADERS: &'static [&'static str] = &[
+        "hash",
+        "name",
+        "save_path",
+        "comment",
+        "created_by",
+        "creation_date",
+        "addition_date",
+        "completion_date",
+        "total_size",
+        "piece_size",
+        "pieces_have",
+        "pieces_num",
+        "total_downloaded",
+        "total_uploaded",
+        "total_wasted",
+        "share_ratio",
+        "ratio_limit",
+        "seeding_time_limit",
+        "nb_connections",
+        "nb_connections_limit",
+        "seeds",
+        "seeds_total",
+        "peers",
+        "peers_total",
+        "time_elapsed",
+        "seeding_time",
+        "last_seen",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        let p = &self.properties;
+
+        vec![
+            self.hash.clone(),
+            self.name.clone(),
+            p.save_path.clone(),
+            p.comment.clone(),
+            p.created_by.clone(),
+            p.creation_date.to_string(),
+            p.addition_date.to_string(),
+            p.completion_date.to_string(),
+            p.total_size.to_string(),
+            p.piece_size.to_string(),
+            p.pieces_have.to_string(),
+            p.pieces_num.to_string(),
+            p.total_downloaded.to_string(),
+            p.total_uploaded.to_string(),
+            p.total_wasted.to_string(),
+            p.share_ratio.to_string(),
+            self.ratio_limit.to_string(),
+            self.seeding_time_limit.to_string(),
+            p.nb_connections.to_string(),
+            p.nb_connections_limit.to_string(),
+            p.seeds.to_string(),
+            p.seeds_total.to_string(),
+            p.peers.to_string(),
+            p.peers_total.to_string(),
+            p.time_elapsed.to_string(),
+            p.seeding_time.to_string(),
+            p.last_seen.to_string(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        for t in items {
+            let p = &t.properties;
+
+            println!("\n   | {}\n   |", t.name);
+            println!("   |  > Hash: {}", t.hash);
+            println!("   |  > Save path: {}", p.save_path);
+            if !p.comment.is_empty() {
+                println!("   |  > Comment: {}", p.comment);
+            }
+            if !p.created_by.is_empty() {
+                println!("   |  > Created by: {}", p.created_by);
+            }
+            println!("   |  > Created on: {}", optional_date(p.creation_date));
+            println!("   |  > Added on: {}", optional_date(p.addition_date));
+            println!("   |  > Completed on: {}", optional_date(p.completion_date));
+            println!("   |  > Last seen complete: {}", optional_date(p.last_seen));
+            println!("   |  > Size: {}", optional_size(p.total_size));
+            println!(
+                "   |  > Pieces: {} x {} (have {})",
+                optional_count(p.pieces_num),
+                optional_size(p.piece_size),
+                optional_count(p.pieces_have)
+            );
+            println!(
+                "   |  > Downloaded: {} ({} this session)",
+                format_size(p.total_downloaded, DECIMAL),
+                format_size(p.total_downloaded_session, DECIMAL)
+            );
+            println!(
+                "   |  > Uploaded: {} ({} this session)",
+                format_size(p.total_uploaded, DECIMAL),
+                format_size(p.total_uploaded_session, DECIMAL)
+            );
+            println!("   |  > Wasted: {}", format_size(p.total_wasted, DECIMAL));
+            println!(
+                "   |  > Share ratio: {:.2} (limit: {})",
+                p.share_ratio,
+                share_limit(t.ratio_limit, |ratio| format!("{:.2}", ratio))
+            );
+            println!(
+                "   |  > Seeding time limit: {}",
+                share_limit(t.seeding_time_limit as f64, |minutes| {
+                    format_duration(minutes as u64 * 60)
+                })
+            );
+            println!(
+                "   |  > Connections: {} (limit: {})",
+                p.nb_connections, p.nb_connections_limit
+            );
+            println!("   |  > Seeds: {} ({} in swarm)", p.seeds, p.seeds_total);
+            println!("   |  > Peers: {} ({} in swarm)", p.peers, p.peers_total);
+            println!(
+                "   |  > Time active: {} (seeding for {})",
+                format_duration(p.time_elapsed),
+                format_duration(p.seeding_time)
+            );
+            println!();
+        }
+    }
+}
+
+// qBittorrent uses -1 for dates that are not known (yet)
+fn optional_date(epoch: i64) -> String {
+    match epoch {
+        ..=0 => "-".to_string(),
+        epoch => epoch_to_datetime(epoch).to_string(),
+    }
+}
+
+// Sizes and piece counts are -1 while the metadata of a magnet link is being downloaded
+fn optional_size(size: i64) -> String {
+    match size {
+        ..=-1 => "-".to_string(),
+        size => format_size(size as u64, DECIMAL),
+    }
+}
+
+fn optional_count(count: i64) -> String {
+    match count {
+        ..=-1 => "-".to_string(),
+        count => count.to_string(),
+    }
+}
+
+impl Render for TorrentFileResponse {
+    const HEADERS: &'static [&'static str] = &[
+        "index",
+        "name",
+        "progress",
+        "size",
+        "first_piece",
+        "last_piece",
+        "priority",
+        "availability",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.index.to_string(),
+            self.name.clone(),
+            self.progress.to_string(),
+            self.size.to_string(),
+            self.piece_range[0].to_string(),
+            self.piece_range[1].to_string(),
+            self.priority.to_string(),
+            self.availability.to_string(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        for file in items {
+            println!("\n\n   | {}\n   |", file.name);
+            println!("   |  > Index: {}", file.index);
+            println!(
+                "   |  > Progress: {:.2}% {}",
+                file.progress * 100.0,
+                progress_render(file.progress)
+            );
+            println!("   |  > Size: {}", format_size(file.size, DECIMAL));
+            println!("   |  > Priority: {}", file.priority_name());
+            // The availability is -1 if it is not known (e.g. for paused torrents)
+            if file.availability >= 0.0 {
+                println!("   |  > Availability: {:.2}%", file.availability * 100.0);
+            }
+        }
+
+        println!("\n\nTorrent contains {} files.", items.len());
+    }
+}
+
+pub fn render_torrent_table(torrents: &[TorrentInfoResponse], columns: &[TorrentColumn]) {
+    let table_columns: Vec<Column> = columns
+        .iter()
+        .map(|column| {
+            let (header, align) = match column {
+                TorrentColumn::Name => ("NAME", Align::Left),
+                TorrentColumn::Hash => ("HASH", Align::Left),
+                TorrentColumn::Size => ("SIZE", Align::Right),
+                TorrentColumn::Progress => ("PROGRESS", Align::Right),
+                TorrentColumn::Dlspeed => ("DOWN", Align::Right),
+                TorrentColumn::Upspeed => ("UP", Align::Right),
+                TorrentColumn::Eta => ("ETA", Align::Right),
+                TorrentColumn::Ratio => ("RATIO", Align::Right),
+                TorrentColumn::State => ("STATE", Align::Left),
+                TorrentColumn::Seeds => ("SEEDS", Align::Right),
+                TorrentColumn::Peers => ("PEERS", Align::Right),
+                TorrentColumn::Category => ("CATEGORY", Align::Left),
+                TorrentColumn::Tags => ("TAGS", Align::Left),
+                TorrentColumn::AddedOn => ("ADDED ON", Align::Left),
+                TorrentColumn::Priority => ("QUEUE", Align::Right),
+            };
+
+            Column {
+                header,
+                align,
+                shrink: *column == TorrentColumn::Name,
+            }
+        })
+        .collect();
+
+    let rows: Vec<Vec<String>> = torrents
+        .iter()
+        .map(|t| columns.iter().map(|c| column_value(t, *c)).collect())
+        .collect();
+
+    print_table(&table_columns, &rows);
+}
+
+fn column_value(t: &TorrentInfoResponse, column: TorrentColumn) -> String {
+    match column {
+        TorrentColumn::Name => t.name.clone(),
+        TorrentColumn::Hash => t.hash.clone(),
+        TorrentColumn::Size => format_size(t.size, DECIMAL),
+        TorrentColumn::Progress => format!("{:.1}%", t.progress * 100.0),
+        TorrentColumn::Dlspeed => format!("{}/s", format_size(t.dlspeed, DECIMAL)),
+        TorrentColumn::Upspeed => format!("{}/s", format_size(t.upspeed, DECIMAL)),
+        TorrentColumn::Eta => match t.eta {
+            INFINITE_ETA => "∞".to_string(),
+            eta => format_duration(eta),
+        },
+        TorrentColumn::Ratio => format!("{:.2}", t.ratio),
+        TorrentColumn::State => t.state.to_string(),
+        TorrentColumn::Seeds => t.num_seeds.to_string(),
+        TorrentColumn::Peers => t.num_leechs.to_string(),
+        TorrentColumn::Category => t.category.clone(),
+        TorrentColumn::Tags => t.tags.clone(),
+        TorrentColumn::AddedOn => epoch_to_datetime(t.added_on).to_string(),
+        TorrentColumn::Priority => match t.priority {
+            ..=0 => "-".to_string(),
+            priority => priority.to_string(),
+        },
+    }
+}
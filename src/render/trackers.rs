@@ -0,0 +1,92 @@
+use crate::backend::TrackerResponse;
+
+use super::{print_table, Align, Column, Render};
+
+impl Render for TrackerResponse {
+    const HEADERS: &'static [&'static str] = &[
+        "url",
+        "status",
+        "tier",
+        "num_seeds",
+        "num_peers",
+        "num_leeches",
+        "num_downloaded",
+        "msg",
+    ];
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.url.clone(),
+            self.status.to_string(),
+            self.tier.to_string(),
+            self.num_seeds.to_string(),
+            self.num_peers.to_string(),
+            self.num_leeches.to_string(),
+            self.num_downloaded.to_string(),
+            self.msg.clone(),
+        ]
+    }
+
+    fn human(items: &[Self]) {
+        let columns = [
+            Column {
+                header: "URL",
+                align: Align::Left,
+                shrink: true,
+            },
+            Column {
+                header: "STATUS",
+                align: Align::Left,
+                shrink: false,
+            },
+            Column {
+                header: "TIER",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "SEEDS",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "PEERS",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "LEECHERS",
+                align: Align::Right,
+                shrink: false,
+            },
+            Column {
+                header: "MESSAGE",
+                align: Align::Left,
+                shrink: false,
+            },
+        ];
+
+        // The api uses -1 for values that don't apply (e.g. the tier of DHT)
+        let number = |value: i64| match value {
+            ..=-1 => "-".to_string(),
+            value => value.to_string(),
+        };
+
+        let rows: Vec<Vec<String>> = items
+            .iter()
+            .map(|t| {
+                vec![
+                    t.url.clone(),
+                    t.status_name().to_string(),
+                    number(t.tier),
+                    number(t.num_seeds),
+                    number(t.num_peers),
+                    number(t.num_leeches),
+                    t.msg.clone(),
+                ]
+            })
+            .collect();
+
+        print_table(&columns, &rows);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::backend::WebSeedResponse;
+
+use super::Render;
+
+impl Render for WebSeedResponse {
+    const HEADERS: &'static [&'static str] = &["url"];
+
+    fn row(&self) -> Vec<String> {
+        vec![self.url.clone()]
+    }
+
+    fn human(items: &[Self]) {
+        if items.is_empty() {
+            println!("The torrent has no web seeds.");
+            return;
+        }
+
+        for webseed in items {
+            println!("{}", webseed.url);
+        }
+    }
+}